@@ -0,0 +1,18 @@
+//! Generates the C header for the API in `src/ffi.rs` into `OUT_DIR`. The committed copy in
+//! `include/` is checked against it by a test in `src/ffi.rs`.
+
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("cannot read cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("cannot generate the C header")
+        .write_to_file(format!("{}/advent_2025.h", out_dir));
+}
@@ -0,0 +1,30 @@
+//! Example day plugin. Build it and copy the library into `plugins/`, and the runner picks it
+//! up as day 12:
+//!
+//! ```bash
+//! cargo build --release --example plugin_day
+//! mkdir -p plugins && cp target/release/examples/libplugin_day.so plugins/
+//! ```
+
+use advent_2025::error::parse;
+use advent_2025::solution::{Day, SolveResult};
+
+pub struct Solution;
+
+impl Day for Solution {
+    /// Count of non-empty lines
+    fn part1(input: &str) -> SolveResult {
+        Ok(input.lines().filter(|line| !line.is_empty()).count().into())
+    }
+
+    /// Sum of the numbers on all lines
+    fn part2(input: &str) -> SolveResult {
+        let mut sum = 0u64;
+        for number in input.split_whitespace() {
+            sum += parse::<u64>(number)?;
+        }
+        Ok(sum.into())
+    }
+}
+
+advent_2025::export_plugin!(12, Solution);
@@ -0,0 +1,61 @@
+/* Advent of Code 2025 solvers, see src/ffi.rs. Generated by cbindgen, do not edit. */
+
+#ifndef ADVENT_2025_H
+#define ADVENT_2025_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of a call
+typedef enum AocStatus {
+  AocStatus_Ok = 0,
+  // A required pointer was null
+  AocStatus_NullArgument = 1,
+  // The day isn't implemented
+  AocStatus_UnknownDay = 2,
+  // The part isn't 1 or 2
+  AocStatus_InvalidPart = 3,
+  // The input isn't UTF-8 or doesn't match the day's schema
+  AocStatus_InvalidInput = 4,
+  // The solver returned an error or panicked
+  AocStatus_Failed = 5,
+  // Panicked outside the solver, this is a bug
+  AocStatus_Panicked = 6,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Number of implemented days
+size_t aoc_num_days(void);
+
+// Write up to `capacity` implemented day numbers to `out`, in order. Returns the total
+// number of days, which may exceed `capacity`.
+//
+// # Safety
+// `out` must be valid for writing `capacity` bytes, or null if `capacity` is 0.
+size_t aoc_days(uint8_t *out, size_t capacity);
+
+// Solve `part` (1 or 2) of `day` on `len` bytes of `input`. On success stores the answer
+// in `*out`, on failure stores the error message, or null if there is none. Strings stored
+// in `*out` must be released with `aoc_string_free`.
+//
+// # Safety
+// `input` must point to `len` readable bytes and `out` must be valid for writes.
+enum AocStatus aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, size_t len, char **out);
+
+// Release a string returned by `aoc_solve`. Null is ignored.
+//
+// # Safety
+// `text` must be null or a string from `aoc_solve` that wasn't freed yet.
+void aoc_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ADVENT_2025_H */
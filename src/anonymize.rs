@@ -0,0 +1,291 @@
+//! Anonymized copies of puzzle inputs, to commit as regression fixtures without publishing
+//! the inputs themselves. The structure is kept: lines, grid dimensions, which characters
+//! can appear where, the digit counts of numbers and the widths of ranges like `11-22`,
+//! while the values are randomized from a seed.
+//!
+//! Fixtures live in `tests/fixtures` as `dayN.txt`, with their answers in `answers.txt` in the
+//! format of the stored answers. `tests/fixtures.rs` checks every day against them.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::answers::{self, Answers};
+use crate::generate::Rng;
+use crate::inspect::{self, Format};
+use crate::runner::{self, Outcome};
+
+/// Where fixtures are written
+pub const FIXTURES_DIR: &str = "tests/fixtures";
+
+/// Seeds tried before giving up on an input the solver won't accept
+const MAX_ATTEMPTS: u64 = 20;
+
+pub fn fixture_path(day: u8) -> PathBuf {
+    PathBuf::from(FIXTURES_DIR).join(format!("day{}.txt", day))
+}
+
+pub fn fixture_answers_path() -> PathBuf {
+    PathBuf::from(FIXTURES_DIR).join(answers::ANSWERS_FILE)
+}
+
+/// Randomize `input` with `seed`, keeping its structure
+pub fn anonymize(input: &str, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    if let Format::Grid { .. } = inspect::inspect(input).format {
+        return anonymize_grid(input, &mut rng);
+    }
+
+    // letters are drawn from the ones seen in the same column, like day1's `L` and `R`
+    let mut letters: BTreeMap<usize, Vec<char>> = BTreeMap::new();
+    for line in input.lines() {
+        for (col, c) in line.chars().enumerate() {
+            if c.is_ascii_alphabetic() {
+                let seen = letters.entry(col).or_default();
+                if !seen.contains(&c) {
+                    seen.push(c);
+                }
+            }
+        }
+    }
+
+    let chars: Vec<char> = input.chars().collect();
+    let digits_end = |start: usize| {
+        (start..chars.len())
+            .find(|&i| !chars[i].is_ascii_digit())
+            .unwrap_or(chars.len())
+    };
+    let mut output = String::with_capacity(input.len());
+    let mut col = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_ascii_digit() {
+            let end = digits_end(i);
+            let first: String = chars[i..end].iter().collect();
+            // a range like `11-22`
+            if chars.get(end) == Some(&'-') && chars.get(end + 1).is_some_and(char::is_ascii_digit)
+            {
+                let last_end = digits_end(end + 1);
+                let last: String = chars[end + 1..last_end].iter().collect();
+                let (a, b) = range(&first, &last, &mut rng);
+                output += &format!("{}-{}", a, b);
+                col += last_end - i;
+                i = last_end;
+            } else {
+                output += &number(&first, &mut rng);
+                col += end - i;
+                i = end;
+            }
+            continue;
+        }
+
+        match c {
+            c if c.is_ascii_alphabetic() => output.push(rng.pick(&letters[&col])),
+            c => output.push(c),
+        }
+        col = if c == '\n' { 0 } else { col + 1 };
+        i += 1;
+    }
+    output
+}
+
+/// A random number with as many digits as `original`, using zeros only if it does
+fn number(original: &str, rng: &mut Rng) -> String {
+    let low = if original.contains('0') { b'0' } else { b'1' };
+    original
+        .bytes()
+        .enumerate()
+        .map(|(i, digit)| match (i, digit) {
+            // leading zeros stay, other numbers don't get one
+            (0, b'0') => '0',
+            (0, _) => char::from(rng.range(u64::from(low.max(b'1')), u64::from(b'9')) as u8),
+            _ => char::from(rng.range(u64::from(low), u64::from(b'9')) as u8),
+        })
+        .collect()
+}
+
+/// A random range with the width of `first-last` and the same digit counts at both ends
+fn range(first: &str, last: &str, rng: &mut Rng) -> (String, String) {
+    let (Ok(a), Ok(b)) = (first.parse::<u64>(), last.parse::<u64>()) else {
+        return (number(first, rng), number(last, rng));
+    };
+    if b < a || first.len() > 18 || last.len() > 18 {
+        return (number(first, rng), number(last, rng));
+    }
+    let width = b - a;
+    let smallest = |digits: usize| match digits {
+        1 => 0,
+        digits => 10u64.pow(digits as u32 - 1),
+    };
+    let largest = |digits: usize| 10u64.pow(digits as u32) - 1;
+    let low = smallest(first.len()).max(smallest(last.len()).saturating_sub(width));
+    let high = largest(first.len()).min(largest(last.len()) - width);
+    // no range of the width keeps both digit counts, like for zero-padded `00-99`
+    if low > high {
+        return (number(first, rng), number(last, rng));
+    }
+    let start = rng.range(low, high);
+    (start.to_string(), (start + width).to_string())
+}
+
+/// Redraw the cells of a grid. Characters that occur once, like a start, stay where they
+/// are, the others are drawn from the characters seen in both the cell's row and column,
+/// weighted by how often they occur in the row.
+fn anonymize_grid(input: &str, rng: &mut Rng) -> String {
+    let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let width = rows[0].len();
+    let mut counts: BTreeMap<char, usize> = BTreeMap::new();
+    for &c in rows.iter().flatten() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let columns: Vec<BTreeSet<char>> = (0..width)
+        .map(|col| rows.iter().map(|row| row[col]).collect())
+        .collect();
+
+    let mut output = String::with_capacity(input.len());
+    for row in &rows {
+        for (col, &c) in row.iter().enumerate() {
+            if counts[&c] == 1 {
+                output.push(c);
+                continue;
+            }
+            let choices: Vec<char> = row
+                .iter()
+                .copied()
+                .filter(|other| counts[other] > 1 && columns[col].contains(other))
+                .collect();
+            output.push(rng.pick(&choices));
+        }
+        output.push('\n');
+    }
+    if !input.ends_with('\n') {
+        output.pop();
+    }
+    output
+}
+
+/// An anonymized input with its answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub input: String,
+    pub answers: Answers,
+    /// Seed the input was made with, the first one the solver accepted
+    pub seed: u64,
+}
+
+/// Anonymize `input` for `day` and solve it. Seeds after `seed` are tried if the solver
+/// rejects or fails on the anonymized input.
+pub fn fixture(day: u8, input: &str, seed: u64) -> Result<Fixture, String> {
+    let mut last_error = String::new();
+    for seed in seed..seed.saturating_add(MAX_ATTEMPTS) {
+        let anonymized = anonymize(input, seed);
+        let result = runner::solve(day, &anonymized, None);
+        if let Outcome::Solved { .. } = result.outcome
+            && let (Ok(part1), Ok(part2)) = (result.answer(1), result.answer(2))
+        {
+            let mut answers = Answers::default();
+            answers.record(1, part1);
+            answers.record(2, part2);
+            return Ok(Fixture {
+                input: anonymized,
+                answers,
+                seed,
+            });
+        }
+        last_error = result.answer(1).err().unwrap_or_default();
+    }
+    Err(format!(
+        "the solver failed on {} anonymized inputs, last: {}",
+        MAX_ATTEMPTS, last_error
+    ))
+}
+
+/// Write the fixture of `day`, replacing any previous one, and record its answers
+pub fn save(day: u8, fixture: &Fixture) -> io::Result<()> {
+    fs::create_dir_all(FIXTURES_DIR)?;
+    fs::write(fixture_path(day), &fixture.input)?;
+    let path = fixture_answers_path();
+    let mut answers =
+        answers::load_answers(&path).map_err(|err| io::Error::other(err.to_string()))?;
+    answers.insert(day, fixture.answers.clone());
+    answers::save_answers(&path, &answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+    const DAY5_INPUT: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32";
+
+    /// Digit count of each number, or each character's class
+    fn shape(text: &str) -> String {
+        text.chars()
+            .map(|c| match c {
+                '0'..='9' => '9',
+                c if c.is_ascii_alphabetic() => 'a',
+                c => c,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_anonymize_text() {
+        let anonymized = anonymize(DAY1_INPUT, 1);
+        assert_ne!(anonymized, DAY1_INPUT);
+        assert_eq!(shape(&anonymized), shape(DAY1_INPUT));
+        assert!(anonymized.lines().all(|line| line.starts_with(['L', 'R'])));
+        assert_eq!(anonymized, anonymize(DAY1_INPUT, 1));
+        assert_ne!(anonymized, anonymize(DAY1_INPUT, 2));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let anonymized = anonymize(DAY5_INPUT, 3);
+        assert_eq!(anonymized.lines().count(), DAY5_INPUT.lines().count());
+        for (line, original) in anonymized.lines().zip(DAY5_INPUT.lines()).take(4) {
+            let width = |line: &str| {
+                let (a, b) = line.split_once('-').unwrap();
+                (
+                    b.parse::<u64>().unwrap() - a.parse::<u64>().unwrap(),
+                    a.len(),
+                    b.len(),
+                )
+            };
+            assert_eq!(width(line), width(original));
+        }
+
+        let (a, b) = range("95", "115", &mut Rng::new(1));
+        assert_eq!((a.len(), b.len()), (2, 3));
+        assert_eq!(b.parse::<u64>().unwrap() - a.parse::<u64>().unwrap(), 20);
+        assert!(!number("987654321111111", &mut Rng::new(1)).contains('0'));
+
+        let (a, b) = range("00", "99", &mut Rng::new(1));
+        assert_eq!((a.len(), b.len()), (2, 2));
+        assert!(a.starts_with('0'));
+    }
+
+    #[test]
+    fn test_anonymize_grid() {
+        let grid = "..S..\n.....\n.^.^.\n.....\n";
+        let anonymized = anonymize(grid, 5);
+        let rows: Vec<&str> = anonymized.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], "..S..");
+        assert_eq!(rows[1], ".....");
+        // splitters never move to a column without one
+        assert!(rows[2].starts_with('.') && rows[2].ends_with('.'));
+        assert!(anonymized.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_fixture() {
+        let made = fixture(1, DAY1_INPUT, 7).unwrap();
+        assert_eq!(made.seed, 7);
+        let result = runner::solve(1, &made.input, None);
+        assert!(result.passed(Some(&made.answers)));
+        assert!(fixture(1, "X1", 7).is_err());
+    }
+}
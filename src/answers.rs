@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::crypt::{self, CryptError};
+use crate::solution::Solution;
+
+/// Default location of the stored answers
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Stored answers for a day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+}
+
+impl Answers {
+    /// Stored answer for a part, as text
+    pub fn part(&self, part: u8) -> &str {
+        match part {
+            1 => &self.part1,
+            _ => &self.part2,
+        }
+    }
+
+    /// Whether an answer matches the stored one, comparing typed values rather than text
+    pub fn matches(&self, part: u8, answer: &Solution) -> bool {
+        let Ok(expected) = self.part(part).parse::<Solution>();
+        answer.matches(&expected)
+    }
+
+    /// Replace the stored answer for a part
+    pub fn record(&mut self, part: u8, answer: &Solution) {
+        let slot = match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        };
+        *slot = answer.to_string();
+    }
+}
+
+/// Load answers, falling back to the encrypted copy when there is no plaintext file.
+/// A missing file counts as no answers.
+/// Format is one line per day: "day: part1, part2"
+pub fn load_answers(path: impl AsRef<Path>) -> Result<HashMap<u8, Answers>, CryptError> {
+    Ok(crypt::read_text(path.as_ref())?
+        .map(|content| parse_answers(&content))
+        .unwrap_or_default())
+}
+
+fn parse_answers(content: &str) -> HashMap<u8, Answers> {
+    let mut answers = HashMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // Format: "day: part1, part2"
+        if let Some((day_str, rest)) = line.split_once(':')
+            && let Ok(day) = day_str.trim().parse::<u8>()
+        {
+            let parts: Vec<&str> = rest.split(',').map(|s| s.trim()).collect();
+            if parts.len() == 2 {
+                answers.insert(
+                    day,
+                    Answers {
+                        part1: parts[0].to_string(),
+                        part2: parts[1].to_string(),
+                    },
+                );
+            }
+        }
+    }
+
+    answers
+}
+
+/// Save answers, sorted by day
+pub fn save_answers(path: impl AsRef<Path>, answers: &HashMap<u8, Answers>) -> io::Result<()> {
+    let mut days: Vec<_> = answers.keys().collect();
+    days.sort();
+
+    let content: String = days
+        .iter()
+        .map(|day| {
+            let a = &answers[day];
+            format!("{}: {}, {}", day, a.part1, a.part2)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    fs::write(path, content + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("1: 3, 6\n\n2: 1227775554, 4174379265\nbogus line\n");
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[&1].part(1), "3");
+        assert_eq!(answers[&2].part(2), "4174379265");
+    }
+
+    #[test]
+    fn test_matches_and_record() {
+        let mut answers = Answers::default();
+        answers.record(1, &Solution::U64(7));
+        assert!(answers.matches(1, &Solution::I32(7)));
+        assert!(!answers.matches(1, &Solution::U64(8)));
+        assert!(!answers.matches(2, &Solution::U64(7)));
+    }
+}
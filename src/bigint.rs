@@ -0,0 +1,490 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Largest power of 10 that fits in a u32 limb, used for decimal conversion
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+/// Arbitrary-precision signed integer for answers that don't fit in 128 bits.
+///
+/// Stored as sign and magnitude, with the magnitude in little-endian base 2^32 limbs.
+/// The magnitude never has trailing zero limbs and zero is never negative, so derived
+/// equality and hashing work on the value.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self::from(1u32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.mag.clone())
+    }
+
+    /// Build from sign and magnitude, restoring the invariants
+    fn from_parts(negative: bool, mut mag: Vec<u32>) -> Self {
+        while mag.last() == Some(&0) {
+            mag.pop();
+        }
+        let negative = negative && !mag.is_empty();
+        Self { negative, mag }
+    }
+
+    /// Convert to an i128 if it fits.
+    pub fn to_i128(&self) -> Option<i128> {
+        let mag = self.to_u128_mag()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(mag)
+        } else {
+            i128::try_from(mag).ok()
+        }
+    }
+
+    /// Convert to a u128 if it fits.
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.negative {
+            return None;
+        }
+        self.to_u128_mag()
+    }
+
+    fn to_u128_mag(&self) -> Option<u128> {
+        if self.mag.len() > 4 {
+            return None;
+        }
+        Some(
+            self.mag
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &limb| (acc << 32) | limb as u128),
+        )
+    }
+
+    /// Multiply the magnitude by a small factor and add a small term, in place
+    fn mul_add_small(&mut self, factor: u32, term: u32) {
+        let mut carry = term as u64;
+        for limb in &mut self.mag {
+            let v = *limb as u64 * factor as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry > 0 {
+            self.mag.push(carry as u32);
+        }
+        *self = Self::from_parts(self.negative, std::mem::take(&mut self.mag));
+    }
+
+    /// Divide the magnitude by a small divisor in place, returning the remainder
+    fn div_rem_small(mag: &mut Vec<u32>, divisor: u32) -> u32 {
+        let mut rem = 0u64;
+        for limb in mag.iter_mut().rev() {
+            let v = (rem << 32) | *limb as u64;
+            *limb = (v / divisor as u64) as u32;
+            rem = v % divisor as u64;
+        }
+        while mag.last() == Some(&0) {
+            mag.pop();
+        }
+        rem as u32
+    }
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let v = limb as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        out.push(v as u32);
+        carry = v >> 32;
+    }
+    if carry > 0 {
+        out.push(carry as u32);
+    }
+    out
+}
+
+/// Subtract magnitudes, `a` must be >= `b`
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut v = limb as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        borrow = 0;
+        if v < 0 {
+            v += 1 << 32;
+            borrow = 1;
+        }
+        out.push(v as u32);
+    }
+    out
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let v = out[i + j] as u64 + x as u64 * y as u64 + carry;
+            out[i + j] = v as u32;
+            carry = v >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+    out
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        Self::from_parts(!self.negative, self.mag)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> Self::Output {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_mag(&self.mag, &rhs.mag));
+        }
+        // differing signs, subtract the smaller magnitude from the larger one
+        match cmp_mag(&self.mag, &rhs.mag) {
+            Ordering::Less => BigInt::from_parts(rhs.negative, sub_mag(&rhs.mag, &self.mag)),
+            _ => BigInt::from_parts(self.negative, sub_mag(&self.mag, &rhs.mag)),
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> Self::Output {
+        BigInt::from_parts(self.negative != rhs.negative, mul_mag(&self.mag, &rhs.mag))
+    }
+}
+
+/// Forward owned and mixed operand variants to the `&BigInt op &BigInt` implementation
+macro_rules! forward_binop {
+    ($trait_:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl $trait_<BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: BigInt) -> Self::Output {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $trait_<&BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: &BigInt) -> Self::Output {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $trait_<BigInt> for &BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: BigInt) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $assign_trait<&BigInt> for BigInt {
+            fn $assign_method(&mut self, rhs: &BigInt) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+
+        impl $assign_trait<BigInt> for BigInt {
+            fn $assign_method(&mut self, rhs: BigInt) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add, AddAssign, add_assign);
+forward_binop!(Sub, sub, SubAssign, sub_assign);
+forward_binop!(Mul, mul, MulAssign, mul_assign);
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a BigInt> for BigInt {
+    fn sum<I: Iterator<Item = &'a BigInt>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a BigInt> for BigInt {
+    fn product<I: Iterator<Item = &'a BigInt>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($type_:ident),*) => {
+        $(impl From<$type_> for BigInt {
+            fn from(n: $type_) -> Self {
+                let n = n as u128;
+                let mag = (0..4).map(|i| (n >> (32 * i)) as u32).collect();
+                Self::from_parts(false, mag)
+            }
+        })*
+    };
+}
+
+macro_rules! impl_from_signed {
+    ($($type_:ident),*) => {
+        $(impl From<$type_> for BigInt {
+            fn from(n: $type_) -> Self {
+                let n = n as i128;
+                let abs = BigInt::from(n.unsigned_abs());
+                Self::from_parts(n < 0, abs.mag)
+            }
+        })*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // peel off 9 decimal digits at a time, least significant chunk first
+        let mut mag = self.mag.clone();
+        let mut chunks = Vec::new();
+        while !mag.is_empty() {
+            chunks.push(Self::div_rem_small(&mut mag, DECIMAL_BASE));
+        }
+
+        let digits = match chunks.split_last() {
+            Some((most, rest)) => rest.iter().rev().fold(most.to_string(), |mut acc, chunk| {
+                acc.push_str(&format!("{chunk:0DECIMAL_DIGITS$}"));
+                acc
+            }),
+            None => "0".to_string(),
+        };
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid digit found in string")
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+
+        let mut n = BigInt::zero();
+        for chunk in digits.as_bytes().chunks(DECIMAL_DIGITS) {
+            let value = chunk
+                .iter()
+                .fold(0u32, |acc, b| acc * 10 + (b - b'0') as u32);
+            n.mul_add_small(10u32.pow(chunk.len() as u32), value);
+        }
+        Ok(Self::from_parts(negative, n.mag))
+    }
+}
+
+/// Serialized as a decimal string, since JSON numbers can't hold arbitrary precision
+impl serde::Serialize for BigInt {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BigInt {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for s in [
+            "0",
+            "7",
+            "-42",
+            "4294967296",
+            "-340282366920938463463374607431768211456",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(format!("{:>6}", BigInt::from(-12)), "   -12");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError));
+        assert_eq!("12a".parse::<BigInt>(), Err(ParseBigIntError));
+    }
+
+    #[test]
+    fn test_from_primitives() {
+        assert_eq!(BigInt::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(BigInt::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(BigInt::from(0u8), BigInt::zero());
+        assert_eq!(BigInt::from(-1i8).to_i128(), Some(-1));
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!((BigInt::from(u128::MAX) + BigInt::one()).to_u128(), None);
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let a = BigInt::from(u64::MAX);
+        assert_eq!((&a + &a).to_string(), "36893488147419103230");
+        assert_eq!(BigInt::from(5) - BigInt::from(8), BigInt::from(-3));
+        assert_eq!(BigInt::from(-5) + BigInt::from(8), BigInt::from(3));
+        assert_eq!(BigInt::from(-5) - BigInt::from(-5), BigInt::zero());
+        assert!(!(BigInt::from(-5) - BigInt::from(-5)).is_negative());
+    }
+
+    #[test]
+    fn test_mul() {
+        // 30! overflows u64
+        let factorial: BigInt = (1..=30u32).map(BigInt::from).product();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(BigInt::from(-3) * BigInt::from(4), BigInt::from(-12));
+        assert_eq!(BigInt::from(-3) * BigInt::zero(), BigInt::zero());
+    }
+
+    #[test]
+    fn test_assign_and_sum() {
+        let mut n = BigInt::from(u128::MAX);
+        n += BigInt::one();
+        n *= &BigInt::from(2);
+        assert_eq!(n, big("680564733841876926926749214863536422912"));
+
+        let total: BigInt = [u128::MAX, u128::MAX].into_iter().map(BigInt::from).sum();
+        assert_eq!(total, big("680564733841876926926749214863536422910"));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut values = vec![
+            big("10000000000000000000000"),
+            big("-3"),
+            big("0"),
+            big("-400"),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                big("-400"),
+                big("-3"),
+                big("0"),
+                big("10000000000000000000000")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let n = big("-123456789012345678901234567890123456789012");
+        let json = serde_json::to_string(&n).unwrap();
+        assert_eq!(json, "\"-123456789012345678901234567890123456789012\"");
+        assert_eq!(serde_json::from_str::<BigInt>(&json).unwrap(), n);
+        assert!(serde_json::from_str::<BigInt>("\"12x\"").is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! Time budget for the whole calendar: the total is split across days, each day is
+//! benchmarked and checked against its share.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::runner::{self, DayResult, Outcome};
+use crate::select::DaySet;
+
+/// Timed runs per day after a warm-up run, the median is kept
+pub const RUNS: usize = 5;
+/// Stop repeating a slow day once its timed runs took this long
+const MAX_BENCH_TIME: Duration = Duration::from_secs(2);
+/// Optimization targets listed by [`Report::render`]
+const MAX_TARGETS_SHOWN: usize = 5;
+
+/// Split `total` across `days` in proportion to their weights, days without a weight
+/// weigh 1
+pub fn shares(days: &[u8], total: Duration, weights: &BTreeMap<u8, f64>) -> Vec<(u8, Duration)> {
+    let weight = |day| weights.get(&day).copied().unwrap_or(1.0);
+    let sum: f64 = days.iter().map(|&day| weight(day)).sum();
+    days.iter()
+        .map(|&day| (day, total.mul_f64(weight(day) / sum)))
+        .collect()
+}
+
+/// Median time spent in the solver for both parts of a day, as the runner measures it, so
+/// schema validation doesn't count against the day's share. The failed result if a run
+/// fails.
+pub fn benchmark(day: u8, input: &str) -> Timing {
+    let warm_up = runner::solve(day, input, None);
+    if !matches!(warm_up.outcome, Outcome::Solved { .. }) {
+        return Timing::Failed(warm_up);
+    }
+
+    let mut times = Vec::with_capacity(RUNS);
+    while times.len() < RUNS && times.iter().sum::<Duration>() < MAX_BENCH_TIME {
+        let result = runner::solve(day, input, None);
+        times.push(result.elapsed);
+        if !matches!(result.outcome, Outcome::Solved { .. }) {
+            return Timing::Failed(result);
+        }
+    }
+    times.sort();
+    Timing::Measured(times[times.len() / 2])
+}
+
+/// How a day did in the benchmark
+#[derive(Debug, Clone)]
+pub enum Timing {
+    Measured(Duration),
+    /// The day failed, so it has no time
+    Failed(DayResult),
+}
+
+/// A day's share of the budget and its time
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub day: u8,
+    pub share: Duration,
+    pub timing: Timing,
+}
+
+impl Entry {
+    pub fn elapsed(&self) -> Option<Duration> {
+        match self.timing {
+            Timing::Measured(elapsed) => Some(elapsed),
+            Timing::Failed(_) => None,
+        }
+    }
+
+    /// Time over the day's share, None if it is within its share
+    pub fn over(&self) -> Option<Duration> {
+        self.elapsed()?
+            .checked_sub(self.share)
+            .filter(|over| !over.is_zero())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub total: Duration,
+    pub entries: Vec<Entry>,
+}
+
+impl Report {
+    /// Benchmark `days` (with their inputs) against `total` split by `weights`
+    pub fn run(days: &[(u8, String)], total: Duration, weights: &BTreeMap<u8, f64>) -> Self {
+        let numbers: Vec<u8> = days.iter().map(|&(day, _)| day).collect();
+        let entries = shares(&numbers, total, weights)
+            .into_iter()
+            .zip(days)
+            .map(|((day, share), (_, input))| Entry {
+                day,
+                share,
+                timing: benchmark(day, input),
+            })
+            .collect();
+        Self { total, entries }
+    }
+
+    /// Total time of the measured days
+    pub fn used(&self) -> Duration {
+        self.entries.iter().filter_map(Entry::elapsed).sum()
+    }
+
+    /// Budget left over, None if the days take longer than the budget
+    pub fn remaining(&self) -> Option<Duration> {
+        self.total.checked_sub(self.used())
+    }
+
+    /// Whether every day ran and the total is within the budget
+    pub fn within_budget(&self) -> bool {
+        self.remaining().is_some() && self.entries.iter().all(|e| e.elapsed().is_some())
+    }
+
+    /// Measured days ranked by the time optimizing them would recover: days over their share
+    /// by how much, then the rest by their time
+    pub fn targets(&self) -> Vec<&Entry> {
+        let mut targets: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.elapsed().is_some())
+            .collect();
+        targets.sort_by_key(|entry| std::cmp::Reverse((entry.over(), entry.elapsed())));
+        targets
+    }
+
+    /// The report as printed by `--budget`: each day's share and time, the `skipped` days
+    /// without an input, the total, and the days most worth optimizing
+    pub fn render(&self, skipped: &[u8]) -> String {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let mut text = format!(
+            "=== Budget: {} ms across {} days ({} runs each, median) ===\n\n",
+            ms(self.total),
+            self.entries.len(),
+            RUNS
+        );
+
+        text += "  Day       Share        Time   Used\n";
+        for entry in &self.entries {
+            let share = ms(entry.share);
+            match &entry.timing {
+                Timing::Measured(elapsed) => {
+                    let used = ms(*elapsed) / share * 100.0;
+                    text += &format!(
+                        "   {:02}  {:>10.4}  {:>10.4}  {:>4.0}%",
+                        entry.day,
+                        share,
+                        ms(*elapsed),
+                        used
+                    );
+                    match entry.over() {
+                        Some(over) => text += &format!("  OVER by {:.4} ms\n", ms(over)),
+                        None => text += "\n",
+                    }
+                }
+                Timing::Failed(result) => {
+                    let message = match &result.outcome {
+                        Outcome::InvalidInput(_) => "invalid input".to_string(),
+                        Outcome::Failed(err) => err.to_string(),
+                        _ => "no answer".to_string(),
+                    };
+                    let first_line = message.lines().next().unwrap_or("");
+                    text += &format!(
+                        "   {:02}  {:>10.4}      FAILED  {}\n",
+                        entry.day, share, first_line
+                    );
+                }
+            }
+        }
+        if !skipped.is_empty() {
+            let skipped: DaySet = skipped.iter().copied().collect();
+            text += &format!("\n  Skipped (no input): {}\n", skipped);
+        }
+
+        text += &match self.remaining() {
+            Some(remaining) => format!(
+                "\n  Total: {:.4} ms of {} ms, {:.4} ms remaining\n",
+                ms(self.used()),
+                ms(self.total),
+                ms(remaining)
+            ),
+            None => format!(
+                "\n  Total: {:.4} ms of {} ms, over budget by {:.4} ms\n",
+                ms(self.used()),
+                ms(self.total),
+                ms(self.used() - self.total)
+            ),
+        };
+
+        let targets = self.targets();
+        if !targets.is_empty() {
+            text += "\n  Optimization targets:\n";
+            let used = ms(self.used());
+            for (rank, entry) in targets.iter().take(MAX_TARGETS_SHOWN).enumerate() {
+                let elapsed = ms(entry.elapsed().unwrap_or_default());
+                let over = match entry.over() {
+                    Some(over) => format!("{:.4} ms over its share, ", ms(over)),
+                    None => String::new(),
+                };
+                text += &format!(
+                    "  {}. Day {:02}: {:.4} ms ({}{:.0}% of the total)\n",
+                    rank + 1,
+                    entry.day,
+                    elapsed,
+                    over,
+                    elapsed / used * 100.0
+                );
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_shares() {
+        let even = shares(&[1, 2, 3, 4], ms(1000), &BTreeMap::new());
+        assert_eq!(
+            even,
+            [(1, ms(250)), (2, ms(250)), (3, ms(250)), (4, ms(250))]
+        );
+        let weighted = shares(&[1, 2], ms(1000), &[(2, 3.0)].into());
+        assert_eq!(weighted, [(1, ms(250)), (2, ms(750))]);
+    }
+
+    #[test]
+    fn test_benchmark() {
+        assert!(matches!(benchmark(1, DAY1_INPUT), Timing::Measured(_)));
+        let Timing::Failed(failed) = benchmark(1, "X1") else {
+            panic!("invalid input was timed");
+        };
+        assert!(matches!(failed.outcome, Outcome::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_report() {
+        let entry = |day, share, elapsed| Entry {
+            day,
+            share: ms(share),
+            timing: Timing::Measured(ms(elapsed)),
+        };
+        let report = Report {
+            total: ms(300),
+            entries: vec![entry(1, 100, 20), entry(2, 100, 150), entry(3, 100, 90)],
+        };
+        assert_eq!(report.used(), ms(260));
+        assert_eq!(report.remaining(), Some(ms(40)));
+        assert!(report.within_budget());
+        assert_eq!(report.entries[1].over(), Some(ms(50)));
+        assert_eq!(report.entries[2].over(), None);
+        let ranked: Vec<u8> = report.targets().iter().map(|e| e.day).collect();
+        assert_eq!(ranked, [2, 3, 1]);
+
+        let over = Report {
+            total: ms(100),
+            ..report
+        };
+        assert_eq!(over.remaining(), None);
+        assert!(!over.within_budget());
+    }
+
+    #[test]
+    fn test_render() {
+        let report = Report {
+            total: ms(300),
+            entries: vec![
+                Entry {
+                    day: 1,
+                    share: ms(150),
+                    timing: Timing::Measured(ms(30)),
+                },
+                Entry {
+                    day: 2,
+                    share: ms(150),
+                    timing: Timing::Measured(ms(200)),
+                },
+            ],
+        };
+        let expected = "\
+=== Budget: 300 ms across 2 days (5 runs each, median) ===
+
+  Day       Share        Time   Used
+   01    150.0000     30.0000    20%
+   02    150.0000    200.0000   133%  OVER by 50.0000 ms
+
+  Skipped (no input): 3-4
+
+  Total: 230.0000 ms of 300 ms, 70.0000 ms remaining
+
+  Optimization targets:
+  1. Day 02: 200.0000 ms (50.0000 ms over its share, 87% of the total)
+  2. Day 01: 30.0000 ms (13% of the total)
+";
+        assert_eq!(report.render(&[3, 4]), expected);
+    }
+
+    #[test]
+    fn test_run() {
+        let report = Report::run(
+            &[(1, DAY1_INPUT.into()), (2, "x".into())],
+            ms(1000),
+            &BTreeMap::new(),
+        );
+        assert_eq!(report.entries[0].share, ms(500));
+        assert!(report.entries[0].elapsed().is_some());
+        assert!(matches!(report.entries[1].timing, Timing::Failed(_)));
+        assert!(!report.within_budget());
+    }
+}
@@ -0,0 +1,233 @@
+//! Cache of answers and timings, so days whose code and input haven't changed can be
+//! reported without solving them again.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::history::fingerprint;
+use crate::runner::{self, DayResult, Outcome};
+use crate::solution::Solution;
+use crate::{SHARED_SOURCES, get_day_solvers};
+
+/// Default location of the cache, gitignored
+pub const CACHE_FILE: &str = ".aoc-cache.json";
+
+/// Build profile the results were computed with. Overflow checks can turn a wrapped answer
+/// into a failure, so they're part of it.
+pub fn build_profile() -> &'static str {
+    match (cfg!(debug_assertions), cfg!(overflow_checks)) {
+        (true, _) => "debug",
+        (false, true) => "release+overflow-checks",
+        (false, false) => "release",
+    }
+}
+
+/// Cache key for a day's results on an input: a hash of the input, the day's source, the
+/// shared modules' sources and the build profile. None if the day isn't implemented.
+pub fn cache_key(day: u8, input: &str) -> Option<u64> {
+    let solvers = get_day_solvers(day)?;
+    let mut bytes = Vec::new();
+    for part in [input, solvers.source, build_profile()]
+        .into_iter()
+        .chain(SHARED_SOURCES.iter().map(|&(_, source)| source))
+    {
+        // length prefixed so that moving text between parts changes the key
+        bytes.extend_from_slice(&(part.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(part.as_bytes());
+    }
+    Some(fingerprint(&bytes))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    key: u64,
+    part1: Option<Solution>,
+    part2: Option<Solution>,
+    elapsed_ms: f64,
+}
+
+/// Cached results of solved days, one entry per day and part selection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cache {
+    entries: BTreeMap<String, Entry>,
+}
+
+/// Entries for a single part are kept apart from runs of both parts, since their timings
+/// differ
+fn entry_name(day: u8, part: Option<u8>) -> String {
+    match part {
+        Some(part) => format!("{}.{}", day, part),
+        None => day.to_string(),
+    }
+}
+
+impl Cache {
+    /// Load the cache, a missing or unreadable file counts as empty
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, content + "\n")
+    }
+
+    /// Cached result for a day, if there is one with the same key
+    pub fn get(&self, day: u8, part: Option<u8>, key: u64) -> Option<DayResult> {
+        let entry = self.entries.get(&entry_name(day, part))?;
+        (entry.key == key).then(|| DayResult {
+            day,
+            outcome: Outcome::Solved {
+                part1: entry.part1.clone(),
+                part2: entry.part2.clone(),
+            },
+            elapsed: Duration::from_secs_f64(entry.elapsed_ms / 1000.0),
+            cached: true,
+        })
+    }
+
+    /// Store a fresh result. Only solved days are cached, failures are always re-run. So are
+    /// NaN and infinite answers, which JSON can't hold: they would be written as `null` and
+    /// make the whole file fail to load.
+    pub fn insert(&mut self, result: &DayResult, part: Option<u8>, key: u64) {
+        if result.cached {
+            return;
+        }
+        let finite = |answer: &Option<Solution>| match answer {
+            Some(Solution::Float(x)) => x.is_finite(),
+            _ => true,
+        };
+        if let Outcome::Solved { part1, part2 } = &result.outcome
+            && finite(part1)
+            && finite(part2)
+        {
+            let entry = Entry {
+                key,
+                part1: part1.clone(),
+                part2: part2.clone(),
+                elapsed_ms: result.elapsed_ms(),
+            };
+            self.entries.insert(entry_name(result.day, part), entry);
+        }
+    }
+}
+
+/// How a run uses the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Report cached results for unchanged days, solve and store the rest
+    Use,
+    /// Solve everything, storing the results
+    Refresh,
+    /// Solve everything, leaving the cache alone
+    Off,
+}
+
+/// Run a day like [`runner::run_day`], going through the cache according to `mode`. Days
+/// that aren't answered from the cache are solved with `solve`, normally [`runner::solve`].
+pub fn run_day(
+    cache: &mut Cache,
+    mode: CacheMode,
+    day: u8,
+    part: Option<u8>,
+    solve: impl FnOnce(u8, &str, Option<u8>) -> DayResult,
+) -> DayResult {
+    let input = match runner::read_input(day) {
+        Ok(Some(input)) => input,
+        // reports the missing or unreadable input without solving
+        _ => return runner::run_day(day, part),
+    };
+    let key = cache_key(day, &input).filter(|_| mode != CacheMode::Off);
+    let Some(key) = key else {
+        return solve(day, &input, part);
+    };
+
+    if mode == CacheMode::Use
+        && let Some(result) = cache.get(day, part, key)
+    {
+        return result;
+    }
+
+    let result = solve(day, &input, part);
+    cache.insert(&result, part, key);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn test_cache_key() {
+        let key = cache_key(1, DAY1_INPUT).unwrap();
+        assert_eq!(cache_key(1, DAY1_INPUT), Some(key));
+        assert_ne!(cache_key(1, "L68"), Some(key));
+        // same input, different day source
+        assert_ne!(cache_key(2, DAY1_INPUT), Some(key));
+        assert_eq!(cache_key(0, DAY1_INPUT), None);
+    }
+
+    #[test]
+    fn test_get_insert() {
+        let mut cache = Cache::default();
+        let key = cache_key(1, DAY1_INPUT).unwrap();
+        let fresh = runner::solve(1, DAY1_INPUT, None);
+        cache.insert(&fresh, None, key);
+
+        let cached = cache.get(1, None, key).unwrap();
+        assert!(cached.cached);
+        assert_eq!(cached.part(2), fresh.part(2));
+        // other keys and part selections miss
+        assert!(cache.get(1, None, key + 1).is_none());
+        assert!(cache.get(1, Some(1), key).is_none());
+    }
+
+    #[test]
+    fn test_failures_not_cached() {
+        let mut cache = Cache::default();
+        let failed = runner::solve(1, "X1", None);
+        cache.insert(&failed, None, 7);
+        assert!(cache.get(1, None, 7).is_none());
+    }
+
+    #[test]
+    fn test_non_finite_not_cached() {
+        let mut cache = Cache::default();
+        cache.insert(&runner::solve(1, DAY1_INPUT, Some(1)), Some(1), 42);
+        let nan = DayResult {
+            day: 2,
+            outcome: Outcome::Solved {
+                part1: Some(Solution::Float(f64::NAN)),
+                part2: Some(Solution::Float(1.5)),
+            },
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        cache.insert(&nan, None, 7);
+        assert!(cache.get(2, None, 7).is_none());
+
+        // the file still loads with the other entries
+        let path = std::env::temp_dir().join(format!("cache-test-{}.json", std::process::id()));
+        cache.save(&path).unwrap();
+        assert_eq!(Cache::load(&path), cache);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_serde() {
+        let mut cache = Cache::default();
+        cache.insert(&runner::solve(1, DAY1_INPUT, Some(1)), Some(1), 42);
+        let json = serde_json::to_string(&cache).unwrap();
+        assert_eq!(serde_json::from_str::<Cache>(&json).unwrap(), cache);
+    }
+}
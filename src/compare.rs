@@ -0,0 +1,458 @@
+//! Comparing exported results across teammates and machines. A run with `--export` writes
+//! its results with the time of a fixed reference benchmark, which is used to scale each
+//! export's times to the first machine, so that faster hardware doesn't win by itself.
+//! Answers are only compared between exports of the same input.
+
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::generate::Rng;
+use crate::history;
+use crate::runner::{DayResult, Outcome};
+
+/// Version of the export format, exports of other versions are rejected
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Timed runs of the reference benchmark, the median is kept
+const REFERENCE_RUNS: usize = 5;
+/// Numbers sorted and hashed by one run of the reference benchmark
+const REFERENCE_SIZE: usize = 1 << 18;
+/// No machine runs the reference benchmark faster, an export claiming so is broken
+const MIN_REFERENCE: Duration = Duration::from_micros(100);
+
+/// Median time of a fixed workload, sorting and hashing pseudo-random numbers, as a
+/// measure of the machine's speed
+pub fn reference_benchmark() -> Duration {
+    let mut rng = Rng::new(0);
+    let numbers: Vec<u64> = (0..REFERENCE_SIZE).map(|_| rng.next_u64()).collect();
+    let mut times: Vec<Duration> = (0..REFERENCE_RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let mut sorted = black_box(numbers.clone());
+            sorted.sort_unstable();
+            let bytes: Vec<u8> = sorted.iter().flat_map(|n| n.to_le_bytes()).collect();
+            black_box(history::fingerprint(&bytes));
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[times.len() / 2]
+}
+
+/// A run's results as written by `--export`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Export {
+    pub version: u32,
+    /// Who or which machine made the run
+    pub name: String,
+    /// Build profile, debug and release times don't compare
+    pub profile: String,
+    /// Time of the reference benchmark on the machine
+    pub reference: Duration,
+    pub days: Vec<ExportedDay>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedDay {
+    /// Fingerprint of the input, None if there was none
+    pub input: Option<u64>,
+    pub result: DayResult,
+}
+
+impl Export {
+    pub fn new(name: &str, profile: &str, reference: Duration) -> Self {
+        Self {
+            version: EXPORT_VERSION,
+            name: name.to_string(),
+            profile: profile.to_string(),
+            reference,
+            days: Vec::new(),
+        }
+    }
+
+    /// Add a day's result, with the fingerprint of the input it was solved on
+    pub fn push(&mut self, result: &DayResult, input: Option<u64>) {
+        self.days.push(ExportedDay {
+            input,
+            result: result.clone(),
+        });
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+        let export: Self = serde_json::from_str(&content)
+            .map_err(|err| format!("{} is not an export: {}", path.display(), err))?;
+        if export.version != EXPORT_VERSION {
+            return Err(format!(
+                "{} has export version {}, expected {}",
+                path.display(),
+                export.version,
+                EXPORT_VERSION
+            ));
+        }
+        if export.reference < MIN_REFERENCE {
+            return Err(format!(
+                "{} has an implausible reference benchmark time of {:?}",
+                path.display(),
+                export.reference
+            ));
+        }
+        Ok(export)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, content + "\n")
+    }
+
+    fn day(&self, day: u8) -> Option<&ExportedDay> {
+        self.days.iter().find(|exported| exported.result.day == day)
+    }
+}
+
+/// A day's result in one export
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cell {
+    /// The day isn't in the export, or had no input
+    Missing,
+    Failed,
+    /// Time scaled to the first export's machine
+    Solved(Duration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub day: u8,
+    /// One cell per export, in the order given
+    pub cells: Vec<Cell>,
+    /// Export with the lowest scaled time, if any solved the day
+    pub fastest: Option<usize>,
+}
+
+/// Exports of the same input that gave different answers to a part
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: u8,
+    /// Fingerprint of the shared input
+    pub input: u64,
+    /// Name and answer of each export that solved the part
+    pub answers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub names: Vec<String>,
+    pub rows: Vec<Row>,
+    pub disagreements: Vec<Disagreement>,
+}
+
+/// Compare exports: times scaled by their reference benchmarks, the fastest per day, and
+/// answers that differ on the same input
+pub fn compare(exports: &[Export]) -> Comparison {
+    let mut days: Vec<u8> = exports
+        .iter()
+        .flat_map(|export| export.days.iter().map(|exported| exported.result.day))
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let base = exports
+        .first()
+        .map_or(Duration::ZERO, |export| export.reference);
+
+    let mut rows = Vec::with_capacity(days.len());
+    let mut disagreements = Vec::new();
+    for &day in &days {
+        let cells: Vec<Cell> = exports
+            .iter()
+            .map(|export| cell(export, day, base))
+            .collect();
+        let fastest = (0..cells.len())
+            .filter_map(|i| match cells[i] {
+                Cell::Solved(elapsed) => Some((elapsed, i)),
+                _ => None,
+            })
+            .min()
+            .map(|(_, i)| i);
+        rows.push(Row {
+            day,
+            cells,
+            fastest,
+        });
+        disagreements.extend(disagreements_on(exports, day));
+    }
+
+    Comparison {
+        names: exports.iter().map(|export| export.name.clone()).collect(),
+        rows,
+        disagreements,
+    }
+}
+
+/// Result of `day` in `export`, with its time scaled to the machine whose reference
+/// benchmark took `base`
+fn cell(export: &Export, day: u8, base: Duration) -> Cell {
+    let Some(exported) = export.day(day) else {
+        return Cell::Missing;
+    };
+    match exported.result.outcome {
+        Outcome::Solved { .. } if export.reference.is_zero() => {
+            Cell::Solved(exported.result.elapsed)
+        }
+        Outcome::Solved { .. } => {
+            let scale = base.as_secs_f64() / export.reference.as_secs_f64();
+            // saturating, a tiny reference that slipped through mustn't overflow
+            let scaled = exported.result.elapsed.as_secs_f64() * scale;
+            Cell::Solved(Duration::try_from_secs_f64(scaled).unwrap_or(Duration::MAX))
+        }
+        Outcome::InvalidInput(_) | Outcome::Failed(_) => Cell::Failed,
+        Outcome::Skipped => Cell::Missing,
+    }
+}
+
+/// The comparison table as printed by `compare`: a column per export, the fastest of each
+/// day marked, then the answers that differ
+pub fn render(exports: &[Export], comparison: &Comparison) -> String {
+    const WIDTH: usize = 14;
+    let row = |label: &str, cells: Vec<String>| {
+        let cells: String = cells
+            .iter()
+            .map(|cell| format!("{:>WIDTH$}", cell))
+            .collect();
+        format!("{:<10}{}\n", label, cells)
+    };
+
+    let mut text = format!(
+        "=== Comparison (times scaled to {}'s machine) ===\n\n",
+        comparison.names[0]
+    );
+    text += &row("", comparison.names.clone());
+    text += &row(
+        "Reference",
+        exports.iter().map(|e| format_ms(e.reference)).collect(),
+    );
+    text += &row(
+        "Profile",
+        exports.iter().map(|e| e.profile.clone()).collect(),
+    );
+    for day in &comparison.rows {
+        let cells = day.cells.iter().enumerate().map(|(i, cell)| match cell {
+            Cell::Missing => "-".to_string(),
+            Cell::Failed => "failed".to_string(),
+            Cell::Solved(elapsed) if day.fastest == Some(i) => format!("*{}", format_ms(*elapsed)),
+            Cell::Solved(elapsed) => format_ms(*elapsed),
+        });
+        text += &row(&format!("Day {:02}", day.day), cells.collect());
+    }
+    text += "\n* fastest for the day\n";
+
+    if exports
+        .iter()
+        .any(|export| export.profile != exports[0].profile)
+    {
+        text += "Warning: the exports were built with different profiles\n";
+    }
+    if !comparison.disagreements.is_empty() {
+        text += "\nDifferent answers on the same input:\n";
+        for disagreement in &comparison.disagreements {
+            let answers: Vec<String> = disagreement
+                .answers
+                .iter()
+                .map(|(name, answer)| format!("{} {}", name, answer))
+                .collect();
+            text += &format!(
+                "  · Day {:02} part {} (input {:016x}): {}\n",
+                disagreement.day,
+                disagreement.part,
+                disagreement.input,
+                answers.join(", ")
+            );
+        }
+    }
+    text
+}
+
+fn format_ms(elapsed: Duration) -> String {
+    format!("{:.4} ms", elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Parts of `day` whose answers differ between exports of the same input
+fn disagreements_on(exports: &[Export], day: u8) -> Vec<Disagreement> {
+    let mut inputs: Vec<u64> = exports
+        .iter()
+        .filter_map(|export| export.day(day)?.input)
+        .collect();
+    inputs.sort();
+    inputs.dedup();
+
+    let mut found = Vec::new();
+    for input in inputs {
+        for part in [1, 2] {
+            let answers: Vec<(&str, _)> = exports
+                .iter()
+                .filter_map(|export| {
+                    let exported = export.day(day).filter(|e| e.input == Some(input))?;
+                    Some((export.name.as_str(), exported.result.part(part)?))
+                })
+                .collect();
+            let Some(&(_, first)) = answers.first() else {
+                continue;
+            };
+            if answers.iter().all(|(_, answer)| answer.matches(first)) {
+                continue;
+            }
+            found.push(Disagreement {
+                day,
+                part,
+                input,
+                answers: answers
+                    .iter()
+                    .map(|(name, answer)| (name.to_string(), answer.to_string()))
+                    .collect(),
+            });
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solution;
+
+    fn solved(day: u8, ms: u64, part2: u64) -> DayResult {
+        DayResult {
+            day,
+            outcome: Outcome::Solved {
+                part1: Some(Solution::U64(1)),
+                part2: Some(Solution::U64(part2)),
+            },
+            elapsed: Duration::from_millis(ms),
+            cached: false,
+        }
+    }
+
+    fn export(name: &str, reference_ms: u64, days: &[(DayResult, Option<u64>)]) -> Export {
+        let mut export = Export::new(name, "release", Duration::from_millis(reference_ms));
+        for (result, input) in days {
+            export.push(result, *input);
+        }
+        export
+    }
+
+    #[test]
+    fn test_compare() {
+        let alice = export(
+            "alice",
+            10,
+            &[(solved(1, 4, 7), Some(1)), (solved(2, 3, 5), Some(2))],
+        );
+        // a machine twice as slow, so its times count half
+        let bob = export(
+            "bob",
+            20,
+            &[(solved(1, 6, 7), Some(1)), (solved(2, 9, 6), Some(3))],
+        );
+        let comparison = compare(&[alice, bob]);
+        assert_eq!(comparison.names, ["alice", "bob"]);
+        assert_eq!(comparison.rows.len(), 2);
+        assert_eq!(
+            comparison.rows[0].cells,
+            [
+                Cell::Solved(Duration::from_millis(4)),
+                Cell::Solved(Duration::from_millis(3))
+            ]
+        );
+        assert_eq!(comparison.rows[0].fastest, Some(1));
+        assert_eq!(comparison.rows[1].fastest, Some(0));
+        // day 2's answers differ, but on different inputs
+        assert!(comparison.disagreements.is_empty());
+
+        // references that slipped past `Export::load`, scaling by 10^28
+        let mut slow = export("slow", 0, &[(solved(1, 4, 7), Some(1))]);
+        slow.reference = Duration::MAX;
+        let mut broken = export("broken", 0, &[(solved(1, 4, 7), Some(1))]);
+        broken.reference = Duration::from_nanos(1);
+        let comparison = compare(&[slow, broken]);
+        assert_eq!(comparison.rows[0].cells[1], Cell::Solved(Duration::MAX));
+    }
+
+    #[test]
+    fn test_disagreements() {
+        let failed = DayResult {
+            day: 2,
+            outcome: Outcome::Failed(crate::error::SolveError::new("boom")),
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        let alice = export(
+            "alice",
+            10,
+            &[(solved(1, 4, 7), Some(1)), (failed, Some(2))],
+        );
+        let bob = export("bob", 10, &[(solved(1, 5, 8), Some(1))]);
+        let comparison = compare(&[alice, bob]);
+        assert_eq!(
+            comparison.disagreements,
+            [Disagreement {
+                day: 1,
+                part: 2,
+                input: 1,
+                answers: vec![("alice".into(), "7".into()), ("bob".into(), "8".into())],
+            }]
+        );
+        assert_eq!(comparison.rows[1].cells, [Cell::Failed, Cell::Missing]);
+        assert_eq!(comparison.rows[1].fastest, None);
+    }
+
+    #[test]
+    fn test_render() {
+        let alice = export("alice", 10, &[(solved(1, 4, 7), Some(1))]);
+        let mut bob = export("bob", 20, &[(solved(1, 6, 8), Some(1))]);
+        bob.profile = "debug".into();
+        let exports = [alice, bob];
+        let expected = "\
+=== Comparison (times scaled to alice's machine) ===
+
+                   alice           bob
+Reference     10.0000 ms    20.0000 ms
+Profile          release         debug
+Day 01         4.0000 ms    *3.0000 ms
+
+* fastest for the day
+Warning: the exports were built with different profiles
+
+Different answers on the same input:
+  · Day 01 part 2 (input 0000000000000001): alice 7, bob 8
+";
+        assert_eq!(render(&exports, &compare(&exports)), expected);
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let path = std::env::temp_dir().join(format!("export-{}.json", std::process::id()));
+        let original = export("alice", 10, &[(solved(1, 4, 7), Some(1))]);
+        original.save(&path).unwrap();
+        let loaded = Export::load(&path).unwrap();
+        assert_eq!(loaded.name, "alice");
+        assert_eq!(loaded.days[0].result.part(2), Some(&Solution::U64(7)));
+
+        fs::write(&path, r#"{"version": 99}"#).unwrap();
+        assert!(Export::load(&path).is_err());
+        let mut tiny = original.clone();
+        tiny.reference = Duration::from_nanos(1);
+        tiny.save(&path).unwrap();
+        let err = Export::load(&path).unwrap_err();
+        assert!(err.contains("implausible"), "{err}");
+        fs::remove_file(&path).unwrap();
+        assert!(reference_benchmark() > Duration::ZERO);
+    }
+}
@@ -0,0 +1,157 @@
+//! Project settings from `aoc.toml`, for things that belong with the repository rather than
+//! on every command line.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::num::NonZeroU64;
+use std::path::Path;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+
+/// Default location of the config, committed with the repository
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub budget: BudgetConfig,
+    pub oracle: OracleConfig,
+    pub leaderboard: LeaderboardConfig,
+}
+
+/// Settings for `--budget`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BudgetConfig {
+    /// Relative weight of each day's share of the budget, days not listed weigh 1
+    #[serde(deserialize_with = "by_day")]
+    pub weights: BTreeMap<u8, f64>,
+}
+
+/// Settings for `--oracle`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OracleConfig {
+    /// Command template for days without their own
+    pub command: Option<String>,
+    /// Command templates of single days
+    #[serde(deserialize_with = "by_day")]
+    pub days: BTreeMap<u8, String>,
+    /// Seconds an oracle may run before it is killed
+    pub timeout: Option<NonZeroU64>,
+}
+
+/// Settings for `leaderboard`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LeaderboardConfig {
+    /// JSON URL of the private leaderboard
+    pub url: Option<String>,
+    /// Our name or member id on the leaderboard
+    pub me: Option<String>,
+}
+
+/// Deserialize a table keyed by day number, TOML keys are always strings
+fn by_day<'de, D, V>(deserializer: D) -> Result<BTreeMap<u8, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    BTreeMap::<String, V>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| match key.parse() {
+            Ok(day @ 1..=25) => Ok((day, value)),
+            _ => Err(D::Error::custom(format!("invalid day {:?}", key))),
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "{}", err),
+            ConfigError::Parse(err) => write!(f, "{}", err.message()),
+            ConfigError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Load the config, a missing file means the defaults
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(ConfigError::Io(err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let config: Config = toml::from_str(text).map_err(ConfigError::Parse)?;
+        if let Some((day, weight)) = config
+            .budget
+            .weights
+            .iter()
+            .find(|&(_, &weight)| !(weight.is_finite() && weight > 0.0))
+        {
+            return Err(ConfigError::Invalid(format!(
+                "budget weight of day {} must be positive, got {}",
+                day, weight
+            )));
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse("[budget.weights]\n7 = 3\n\"8\" = 0.5\n").unwrap();
+        assert_eq!(config.budget.weights, [(7, 3.0), (8, 0.5)].into());
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+
+        let config = Config::parse(
+            "[oracle]\ncommand = \"python3 day{day}.py\"\n[oracle.days]\n7 = \"./day7\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.oracle.command.as_deref(),
+            Some("python3 day{day}.py")
+        );
+        assert_eq!(config.oracle.days, [(7, "./day7".to_string())].into());
+        let config = Config::parse("[oracle]\ntimeout = 5\n").unwrap();
+        assert_eq!(config.oracle.timeout, NonZeroU64::new(5));
+
+        let config =
+            Config::parse("[leaderboard]\nurl = \"http://localhost/lb.json\"\nme = \"42\"\n")
+                .unwrap();
+        assert_eq!(
+            config.leaderboard.url.as_deref(),
+            Some("http://localhost/lb.json")
+        );
+        assert_eq!(config.leaderboard.me.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Config::parse("[budget.weights]\n26 = 1\n").is_err());
+        assert!(Config::parse("[budget.weights]\n3 = 0\n").is_err());
+        assert!(Config::parse("[budget]\nweight = 1\n").is_err());
+        assert!(Config::parse("[oracle]\ntimeout = 0\n").is_err());
+    }
+}
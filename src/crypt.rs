@@ -0,0 +1,246 @@
+//! Encrypted-at-rest storage for puzzle inputs and answers, so they can be committed without
+//! sharing them. `input/dayN/input.txt` and `answers.txt` are stored next to their plaintext
+//! path as `input.txt.enc` and `answers.txt.enc`, encrypted with XChaCha20-Poly1305 under a
+//! 256-bit key from `$AOC_KEY` (hex) or a key file.
+
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, Generate, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+/// Environment variable holding the key as 64 hex digits
+pub const KEY_ENV: &str = "AOC_KEY";
+/// Environment variable overriding the key file location
+pub const KEY_FILE_ENV: &str = "AOC_KEY_FILE";
+/// Key file used when neither variable is set, gitignored
+pub const DEFAULT_KEY_FILE: &str = ".aoc-key";
+/// Extension appended to the plaintext path for the encrypted file
+pub const EXTENSION: &str = "enc";
+
+/// Start of every encrypted file, followed by the nonce and the ciphertext
+const MAGIC: &[u8] = b"AOCENC1\n";
+const NONCE_LEN: usize = 24;
+
+#[derive(Debug)]
+pub enum CryptError {
+    /// No key in the environment or key file
+    NoKey,
+    /// The key isn't 64 hex digits
+    BadKey,
+    /// The file isn't in the encrypted format
+    NotEncrypted,
+    /// Wrong key, or the file was modified
+    Decrypt,
+    Io(io::Error),
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptError::NoKey => write!(
+                f,
+                "no key found, set ${} or create {} (`keygen`)",
+                KEY_ENV,
+                key_file().display()
+            ),
+            CryptError::BadKey => write!(f, "key must be 64 hex digits"),
+            CryptError::NotEncrypted => write!(f, "not an encrypted file"),
+            CryptError::Decrypt => write!(f, "decryption failed, wrong key or corrupted file"),
+            CryptError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CryptError {}
+
+impl From<io::Error> for CryptError {
+    fn from(err: io::Error) -> Self {
+        CryptError::Io(err)
+    }
+}
+
+/// Key file location, `$AOC_KEY_FILE` or `.aoc-key`
+pub fn key_file() -> PathBuf {
+    std::env::var_os(KEY_FILE_ENV).map_or_else(|| PathBuf::from(DEFAULT_KEY_FILE), PathBuf::from)
+}
+
+/// A 256-bit encryption key
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Generate a random key
+    pub fn generate() -> Self {
+        Self(chacha20poly1305::Key::generate().into())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, CryptError> {
+        let hex = hex.trim().as_bytes();
+        if hex.len() != 64 {
+            return Err(CryptError::BadKey);
+        }
+        let mut key = [0; 32];
+        for (byte, pair) in key.iter_mut().zip(hex.chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| CryptError::BadKey)?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| CryptError::BadKey)?;
+        }
+        Ok(Self(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Load the key from `$AOC_KEY`, or the key file if the variable isn't set
+    pub fn load() -> Result<Self, CryptError> {
+        if let Ok(hex) = std::env::var(KEY_ENV) {
+            return Self::from_hex(&hex);
+        }
+        match fs::read_to_string(key_file()) {
+            Ok(hex) => Self::from_hex(&hex),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Err(CryptError::NoKey),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Write the key to a new key file, readable only by the owner on unix.
+    /// Fails if the file already exists rather than replacing a key in use.
+    pub fn save(&self, path: &Path) -> Result<(), CryptError> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path)?;
+        writeln!(file, "{}", self.to_hex())?;
+        Ok(())
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.0.into())
+    }
+
+    /// Encrypt with a fresh random nonce
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = XNonce::generate();
+        let ciphertext = self
+            .cipher()
+            .encrypt(&nonce, plaintext)
+            .expect("encryption of an in-memory buffer can't fail");
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, blob: &[u8]) -> Result<Vec<u8>, CryptError> {
+        let rest = blob.strip_prefix(MAGIC).ok_or(CryptError::NotEncrypted)?;
+        if rest.len() < NONCE_LEN {
+            return Err(CryptError::NotEncrypted);
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let nonce = XNonce::try_from(nonce).map_err(|_| CryptError::NotEncrypted)?;
+        self.cipher()
+            .decrypt(&nonce, ciphertext)
+            .map_err(|_| CryptError::Decrypt)
+    }
+}
+
+/// Path of the encrypted file for a plaintext path, `input.txt` -> `input.txt.enc`
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Read a text file, falling back to decrypting its encrypted copy when there is no
+/// plaintext. Returns None if neither exists.
+pub fn read_text(path: &Path) -> Result<Option<String>, CryptError> {
+    match fs::read_to_string(path) {
+        Ok(text) => return Ok(Some(text)),
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+        Err(_) => {}
+    }
+
+    let blob = match fs::read(encrypted_path(path)) {
+        Ok(blob) => blob,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let plaintext = Key::load()?.decrypt(&blob)?;
+    String::from_utf8(plaintext)
+        .map(Some)
+        .map_err(|_| CryptError::Decrypt)
+}
+
+/// Encrypt the plaintext file at `path` into its encrypted copy, keeping the plaintext
+pub fn encrypt_file(key: &Key, path: &Path) -> Result<PathBuf, CryptError> {
+    let encrypted = encrypted_path(path);
+    fs::write(&encrypted, key.encrypt(&fs::read(path)?))?;
+    Ok(encrypted)
+}
+
+/// Decrypt the encrypted copy of `path` back into the plaintext file
+pub fn decrypt_file(key: &Key, path: &Path) -> Result<(), CryptError> {
+    let plaintext = key.decrypt(&fs::read(encrypted_path(path))?)?;
+    fs::write(path, plaintext)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let key = Key::generate();
+        let blob = key.encrypt(b"L68\nL30\n");
+        assert!(blob.starts_with(MAGIC));
+        assert_eq!(key.decrypt(&blob).unwrap(), b"L68\nL30\n");
+        // nonces are random, so the same plaintext encrypts differently
+        assert_ne!(key.encrypt(b"L68\nL30\n"), blob);
+    }
+
+    #[test]
+    fn test_wrong_key_or_tampered() {
+        let key = Key::generate();
+        let mut blob = key.encrypt(b"secret");
+        assert!(matches!(
+            Key::generate().decrypt(&blob),
+            Err(CryptError::Decrypt)
+        ));
+        *blob.last_mut().unwrap() ^= 1;
+        assert!(matches!(key.decrypt(&blob), Err(CryptError::Decrypt)));
+        assert!(matches!(
+            key.decrypt(b"plain text"),
+            Err(CryptError::NotEncrypted)
+        ));
+    }
+
+    #[test]
+    fn test_hex() {
+        let key = Key::generate();
+        assert_eq!(Key::from_hex(&key.to_hex()).unwrap().0, key.0);
+        assert!(matches!(Key::from_hex("abc"), Err(CryptError::BadKey)));
+        assert!(matches!(
+            Key::from_hex(&"zz".repeat(32)),
+            Err(CryptError::BadKey)
+        ));
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let dir = std::env::temp_dir().join(format!("crypt-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.txt");
+        fs::write(&path, "1,2,3\n").unwrap();
+
+        let key = Key::generate();
+        let encrypted = encrypt_file(&key, &path).unwrap();
+        assert_eq!(encrypted, dir.join("input.txt.enc"));
+        fs::remove_file(&path).unwrap();
+        decrypt_file(&key, &path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1,2,3\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,4 +1,6 @@
-use crate::solution::Day;
+use crate::error::{Context, SolveError, parse};
+use crate::schema::{Schema, Token};
+use crate::solution::{Day, SolutionPair, SolveResult};
 use std::collections::HashMap;
 
 const MAX_STEP: isize = 100; // where 100 == 0 basically
@@ -32,57 +34,71 @@ impl Dial {
             ceil_div(prev_number, MAX_STEP) - ceil_div(new_number, MAX_STEP)
         };
 
-        self.counts_crossed_zero += wraps.abs() as usize;
+        self.counts_crossed_zero += wraps.unsigned_abs();
         *self.counts.entry(self.number).or_insert(0) += 1;
     }
 
-    fn apply_command(&mut self, command: &str) {
+    fn apply_command(&mut self, command: &str) -> Result<(), SolveError> {
         let rotation = command
             .chars()
             .next()
-            .expect("Command must have a character as first element");
-        let number = command[1..]
-            .parse::<isize>()
-            .expect("Command must have a number as second element");
+            .ok_or_else(|| SolveError::at(command, "empty command"))?;
+        let (rotation_str, number_str) = command.split_at(rotation.len_utf8());
+        let number = parse::<isize>(number_str)?;
         match rotation {
-            'L' => self.rotate(-1 * number),
+            'L' => self.rotate(-number),
             'R' => self.rotate(number),
-            rot => panic!("Unknown command {rot}"),
+            rot => {
+                return Err(SolveError::at(
+                    rotation_str,
+                    format!("unknown rotation {rot:?}, expected 'L' or 'R'"),
+                ));
+            }
         }
+        Ok(())
     }
 
-    fn apply_commands(&mut self, commands: &str) {
+    fn apply_commands(&mut self, commands: &str) -> Result<(), SolveError> {
         for command in commands.split_whitespace() {
-            self.apply_command(command);
+            self.apply_command(command)
+                .context(format!("while applying command {command:?}"))?;
         }
+        Ok(())
     }
 }
 
-fn run_dial(input: &str) -> Dial {
+fn run_dial(input: &str) -> Result<Dial, SolveError> {
     let mut dial = Dial::new(50);
-    dial.apply_commands(input);
-    dial
+    dial.apply_commands(input)?;
+    Ok(dial)
+}
+
+/// How many times the dial stopped at 0
+fn zero_count(dial: &Dial) -> usize {
+    dial.counts.get(&0).copied().unwrap_or(0)
 }
 
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
-        let dial = run_dial(input);
-        (*dial.counts.get(&0).unwrap()).into()
+    const SCHEMA: Schema = Schema::Lines(&[Token::OneOf("LR"), Token::Uint]);
+
+    fn part1(input: &str) -> SolveResult {
+        let dial = run_dial(input)?;
+        Ok(zero_count(&dial).into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
-        let dial = run_dial(input);
-        dial.counts_crossed_zero.into()
+    fn part2(input: &str) -> SolveResult {
+        let dial = run_dial(input)?;
+        Ok(dial.counts_crossed_zero.into())
     }
 
     // Override default to avoid running dial twice
-    fn solve(input: &str) -> crate::solution::SolutionPair {
-        let dial = run_dial(input);
-        let sol1 = *dial.counts.get(&0).unwrap();
+    fn solve(input: &str) -> Result<SolutionPair, SolveError> {
+        let dial = run_dial(input)?;
+        let sol1 = zero_count(&dial);
         let sol2 = dial.counts_crossed_zero;
-        (sol1.into(), sol2.into())
+        Ok((sol1.into(), sol2.into()))
     }
 }
 
@@ -114,14 +130,14 @@ mod tests {
     #[test]
     fn test_apply_command_left_overflow() {
         let mut dial = Dial::new(50);
-        dial.apply_command("L68");
+        dial.apply_command("L68").unwrap();
         assert_eq!(dial.number, 82);
     }
 
     #[test]
     fn test_apply_command_count_zero() {
         let mut dial = Dial::new(50);
-        dial.apply_command("R1000");
+        dial.apply_command("R1000").unwrap();
         assert_eq!(dial.number, 50);
         assert_eq!(dial.counts_crossed_zero, 10);
     }
@@ -140,12 +156,24 @@ L99
 R14
 L82";
         let mut dial = Dial::new(50);
-        dial.apply_commands(input);
+        dial.apply_commands(input).unwrap();
         assert_eq!(dial.number, 32);
         assert_eq!(dial.counts.get(&0).unwrap(), &3);
         assert_eq!(dial.counts_crossed_zero, 6);
     }
 
+    #[test]
+    fn test_unknown_command() {
+        let input = "L68\nX30";
+        let err = Dial::new(50)
+            .apply_commands(input)
+            .unwrap_err()
+            .locate(input);
+        assert_eq!(err.message(), "unknown rotation 'X', expected 'L' or 'R'");
+        let loc = err.location().unwrap();
+        assert_eq!((loc.line, loc.columns.clone()), (2, 0..1));
+    }
+
     // === EDGE CASE TESTS ===
 
     #[test]
@@ -1,7 +1,9 @@
 use rayon::prelude::*;
 use std::ops::RangeInclusive;
 
-use crate::solution::Day;
+use crate::error::{SolveError, parse};
+use crate::schema::{Schema, Token};
+use crate::solution::{Day, SolveResult};
 
 /// get divisors of a number (with square root optimization)
 fn divisors(n: u32) -> Vec<u32> {
@@ -96,30 +98,39 @@ fn get_invalid_ids_strict(range: RangeInclusive<u64>) -> impl Iterator<Item = u6
     range.filter(|id| is_id_invalid_strict(*id))
 }
 
-fn get_ranges(input: &str) -> impl Iterator<Item = RangeInclusive<u64>> {
-    return input.trim().split(',').filter_map(|ids| {
-        let mut parts = ids.split('-').map(|id| id.parse::<u64>());
-        let first = parts.next().expect("Missing first ID").ok()?;
-        let last = parts.next().expect("Missing last ID").ok()?;
-
-        Some(first..=last)
-    });
+fn get_ranges(input: &str) -> Result<Vec<RangeInclusive<u64>>, SolveError> {
+    input
+        .trim()
+        .split(',')
+        .map(|ids| {
+            let (first, last) = ids
+                .split_once('-')
+                .ok_or_else(|| SolveError::at(ids, "expected a range like 11-22"))?;
+
+            Ok(parse(first)?..=parse(last)?)
+        })
+        .collect()
 }
 
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
-        let ranges = get_ranges(input).collect::<Vec<_>>();
+    const SCHEMA: Schema = Schema::Lines(&[Token::List {
+        item: &[Token::Uint, Token::Lit("-"), Token::Uint],
+        sep: &[Token::Lit(",")],
+    }]);
+
+    fn part1(input: &str) -> SolveResult {
+        let ranges = get_ranges(input)?;
         let sum_invalid: u64 = ranges
             .iter()
             .flat_map(|range| get_invalid_ids(range.clone()))
             .sum();
-        sum_invalid.into()
+        Ok(sum_invalid.into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
-        let ranges = get_ranges(input).collect::<Vec<_>>();
+    fn part2(input: &str) -> SolveResult {
+        let ranges = get_ranges(input)?;
         let sum_invalid: u64 = ranges
             .iter()
             .collect::<Vec<_>>()
@@ -127,7 +138,7 @@ impl Day for Solution {
             // 9800x3d cpu
             .flat_map(|range| get_invalid_ids_strict(range.clone()).collect::<Vec<_>>())
             .sum();
-        sum_invalid.into()
+        Ok(sum_invalid.into())
     }
 }
 
@@ -156,6 +167,8 @@ mod tests {
     1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
     824824821-824824827,2121212118-2121212124";
         let sum_invalid: u64 = get_ranges(input)
+            .unwrap()
+            .into_iter()
             .flat_map(|range| get_invalid_ids(range))
             .sum();
         assert_eq!(sum_invalid, 1227775554);
@@ -201,6 +214,8 @@ mod tests {
     1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
     824824821-824824827,2121212118-2121212124";
         let sum_invalid: u64 = get_ranges(input)
+            .unwrap()
+            .into_iter()
             .flat_map(|range| get_invalid_ids_strict(range))
             .sum();
         assert_eq!(sum_invalid, 4174379265);
@@ -1,6 +1,11 @@
 use std::collections::LinkedList;
 
-use crate::solution::Day;
+use crate::error::SolveError;
+use crate::schema::{Schema, Token};
+use crate::solution::{Day, SolutionPair, SolveResult};
+
+/// Batteries turned on in each bank for part 2
+const BATTERIES: usize = 12;
 
 fn find_largest_pair_linear(line: &[u8]) -> u8 {
     let mut first = 0;
@@ -105,12 +110,16 @@ fn find_largest_stack(line: &[u8], keep_count: usize) -> u64 {
     stack.iter().fold(0, |acc, &d| acc * 10 + (d as u64))
 }
 
-fn process_input(input: &str) -> Vec<Vec<u8>> {
+fn process_input(input: &str) -> Result<Vec<Vec<u8>>, SolveError> {
     input
         .lines()
         .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as u8)
+            line.char_indices()
+                .map(|(i, c)| {
+                    c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                        SolveError::at(&line[i..i + c.len_utf8()], format!("{c:?} is not a digit"))
+                    })
+                })
                 .collect()
         })
         .collect()
@@ -124,35 +133,46 @@ fn solve_part1(input: &[Vec<u8>]) -> u64 {
         .sum()
 }
 
-fn solve_part2(input: &[Vec<u8>]) -> u64 {
+fn solve_part2(input: &[Vec<u8>]) -> Result<u64, SolveError> {
     input
         .iter()
-        // .map(|line| find_largest_twelve_linear(line))
-        .map(|line| find_largest_stack(line, 12))
-        .map(u64::from)
+        .enumerate()
+        .map(|(i, line)| {
+            if line.len() < BATTERIES {
+                return Err(SolveError::at_position(
+                    i + 1,
+                    0..line.len(),
+                    format!("bank has {} batteries, need {}", line.len(), BATTERIES),
+                ));
+            }
+            // Ok(find_largest_twelve_linear(line))
+            Ok(find_largest_stack(line, BATTERIES))
+        })
         .sum()
 }
 
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
-        let processed = process_input(input);
-        solve_part1(&processed).into()
+    const SCHEMA: Schema = Schema::Lines(&[Token::Uint]);
+
+    fn part1(input: &str) -> SolveResult {
+        let processed = process_input(input)?;
+        Ok(solve_part1(&processed).into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
-        let processed = process_input(input);
-        solve_part2(&processed).into()
+    fn part2(input: &str) -> SolveResult {
+        let processed = process_input(input)?;
+        Ok(solve_part2(&processed)?.into())
     }
 
     // Override to avoid parsing input twice
-    fn solve(input: &str) -> crate::solution::SolutionPair {
-        let processed = process_input(input);
-        (
+    fn solve(input: &str) -> Result<SolutionPair, SolveError> {
+        let processed = process_input(input)?;
+        Ok((
             solve_part1(&processed).into(),
-            solve_part2(&processed).into(),
-        )
+            solve_part2(&processed)?.into(),
+        ))
     }
 }
 
@@ -168,7 +188,7 @@ mod tests {
 
     #[test]
     fn test_input_1() {
-        assert_eq!(solve_part1(&process_input(TEST_INPUT)), 357);
+        assert_eq!(solve_part1(&process_input(TEST_INPUT).unwrap()), 357);
     }
 
     #[test]
@@ -191,6 +211,22 @@ mod tests {
 
     #[test]
     fn test_input_2() {
-        assert_eq!(solve_part2(&process_input(TEST_INPUT)), 3121910778619);
+        assert_eq!(
+            solve_part2(&process_input(TEST_INPUT).unwrap()),
+            Ok(3121910778619)
+        );
+    }
+
+    #[test]
+    fn test_short_bank() {
+        let input = "987654321111111\n98765";
+        let err = Solution::part2(input).unwrap_err().locate(input);
+        assert_eq!(err.location().unwrap().line, 2);
+        assert!(err.message().contains("5 batteries"), "{err}");
+    }
+
+    #[test]
+    fn test_schema() {
+        assert!(Solution::SCHEMA.validate(TEST_INPUT).is_ok());
     }
 }
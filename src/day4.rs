@@ -1,10 +1,15 @@
 use std::collections::HashSet;
 
 use crate::grid::Grid;
-use crate::solution::Day;
+use crate::position::Position;
+use crate::schema::Schema;
+use crate::solution::{Day, SolveResult};
+use crate::visualize::{self, Frame};
 
 const ROLL: u8 = b'@';
 const BLANK: u8 = b'.';
+/// Shown in place of rolls removed in the current wave
+const REMOVED: char = 'x';
 
 type Pos = (usize, usize);
 
@@ -33,8 +38,13 @@ fn remove_all_rolls(grid: &mut Grid<u8>) -> usize {
         .map(|(x, y, _)| (x, y))
         .collect();
 
+    visualize::emit(|| Frame::new(grid).caption("initial rolls"));
+    let recording = visualize::enabled();
+    let mut wave = 0;
+
     while !candidates.is_empty() {
         let mut next_candidates = HashSet::new();
+        let mut removed = Vec::new();
 
         for (x, y) in candidates.drain() {
             if grid[(x, y)] != ROLL {
@@ -50,9 +60,19 @@ fn remove_all_rolls(grid: &mut Grid<u8>) -> usize {
                 total_removed += 1;
                 grid[(x, y)] = BLANK;
                 next_candidates.extend(adjacent);
+                if recording {
+                    removed.push(Position::new(x, y));
+                }
             }
         }
 
+        wave += 1;
+        visualize::emit(|| {
+            Frame::new(grid)
+                .caption(format!("wave {}: removed {} rolls", wave, removed.len()))
+                .highlight(removed, REMOVED)
+        });
+
         candidates = next_candidates;
     }
 
@@ -62,14 +82,16 @@ fn remove_all_rolls(grid: &mut Grid<u8>) -> usize {
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
+    const SCHEMA: Schema = Schema::Grid(".@");
+
+    fn part1(input: &str) -> SolveResult {
         let grid = Grid::parse(input);
-        count_accessible(&grid).into()
+        Ok(count_accessible(&grid).into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
+    fn part2(input: &str) -> SolveResult {
         let mut grid = Grid::parse(input);
-        remove_all_rolls(&mut grid).into()
+        Ok(remove_all_rolls(&mut grid).into())
     }
 }
 
@@ -102,4 +124,22 @@ mod tests {
         let total_removed = remove_all_rolls(&mut grid);
         assert_eq!(total_removed, 43);
     }
+
+    #[test]
+    fn test_visualize() {
+        let mut grid = Grid::parse(TEST_INPUT);
+        let (total_removed, frames) = visualize::capture(|| remove_all_rolls(&mut grid));
+        assert_eq!(frames[0].caption, "initial rolls");
+        let highlighted: usize = frames
+            .iter()
+            .flat_map(|f| &f.overlays)
+            .map(|o| o.positions.len())
+            .sum();
+        assert_eq!(highlighted, total_removed);
+    }
+
+    #[test]
+    fn test_schema() {
+        assert!(Solution::SCHEMA.validate(TEST_INPUT).is_ok());
+    }
 }
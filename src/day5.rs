@@ -1,25 +1,27 @@
 use std::ops::RangeInclusive;
 
-use crate::solution::Day;
+use crate::error::{Context, SolveError, parse};
+use crate::schema::{Schema, Token};
+use crate::solution::{Day, SolutionPair, SolveResult};
 
-fn parse_input(input: &str) -> (Vec<RangeInclusive<usize>>, Vec<usize>) {
-    let (ranges, values) = input
-        .split_once("\n\n")
-        .expect("Invalid input, could not find split");
+fn parse_input(input: &str) -> Result<(Vec<RangeInclusive<usize>>, Vec<usize>), SolveError> {
+    let (ranges, values) = input.split_once("\n\n").ok_or_else(|| {
+        SolveError::new("could not find the blank line between ranges and ingredients")
+    })?;
 
     let mut ranges = ranges
         .lines()
         .map(|line| {
-            let parts = line
+            let (start, end) = line
                 .trim()
                 .split_once('-')
-                .expect("Invalid line, could not find - to split");
-            let start: usize = parts.0.parse().expect("Invalid start");
-            let end: usize = parts.1.parse().expect("Invalid end");
+                .ok_or_else(|| SolveError::at(line, "expected a range like 3-5"))?;
+            let start: usize = parse(start).context("while parsing range start")?;
+            let end: usize = parse(end).context("while parsing range end")?;
             // handle ranges that are backwards
-            start.min(end)..=start.max(end)
+            Ok(start.min(end)..=start.max(end))
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, SolveError>>()?;
 
     // Ensure ranges are sorted by start
     ranges.sort_by_key(|range| *range.start());
@@ -41,10 +43,10 @@ fn parse_input(input: &str) -> (Vec<RangeInclusive<usize>>, Vec<usize>) {
 
     let values = values
         .lines()
-        .map(|line| line.trim().parse().expect("Invalid value"))
-        .collect::<Vec<usize>>();
+        .map(|line| parse(line.trim()).context("while parsing ingredient"))
+        .collect::<Result<Vec<usize>, SolveError>>()?;
 
-    (merged_ranges, values)
+    Ok((merged_ranges, values))
 }
 
 // Naive linear search, first attempt
@@ -88,22 +90,27 @@ fn count_all_fresh(ranges: &[RangeInclusive<usize>]) -> usize {
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
-        let (ranges, values) = parse_input(input);
-        filter_ingredients_bs(&ranges, &values).len().into()
+    const SCHEMA: Schema = Schema::Sections(&[
+        Schema::Lines(&[Token::Uint, Token::Lit("-"), Token::Uint]),
+        Schema::Lines(&[Token::Uint]),
+    ]);
+
+    fn part1(input: &str) -> SolveResult {
+        let (ranges, values) = parse_input(input)?;
+        Ok(filter_ingredients_bs(&ranges, &values).len().into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
-        let (ranges, _) = parse_input(input);
-        count_all_fresh(&ranges).into()
+    fn part2(input: &str) -> SolveResult {
+        let (ranges, _) = parse_input(input)?;
+        Ok(count_all_fresh(&ranges).into())
     }
 
     // Override to avoid parsing twice
-    fn solve(input: &str) -> crate::solution::SolutionPair {
-        let (ranges, values) = parse_input(input);
+    fn solve(input: &str) -> Result<SolutionPair, SolveError> {
+        let (ranges, values) = parse_input(input)?;
         let ingredients = filter_ingredients_bs(&ranges, &values);
         let count = count_all_fresh(&ranges);
-        (ingredients.len().into(), count.into())
+        Ok((ingredients.len().into(), count.into()))
     }
 }
 
@@ -125,15 +132,28 @@ mod tests {
 
     #[test]
     fn test_solve() {
-        let (ranges, values) = parse_input(TEST_INPUT);
+        let (ranges, values) = parse_input(TEST_INPUT).unwrap();
         let ingredients = filter_ingredients_bs(&ranges, &values);
         assert_eq!(ingredients.len(), 3);
     }
 
     #[test]
     fn test_solve_part_2() {
-        let (ranges, _) = parse_input(TEST_INPUT);
+        let (ranges, _) = parse_input(TEST_INPUT).unwrap();
         let count = count_all_fresh(&ranges);
         assert_eq!(count, 14);
     }
+
+    #[test]
+    fn test_invalid_value() {
+        let input = "3-5\n\n1\n5x";
+        let err = parse_input(input).unwrap_err().locate(input);
+        assert_eq!(err.location().unwrap().line, 4);
+        assert_eq!(err.location().unwrap().columns, 0..2);
+    }
+
+    #[test]
+    fn test_schema() {
+        assert!(Solution::SCHEMA.validate(TEST_INPUT).is_ok());
+    }
 }
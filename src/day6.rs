@@ -1,45 +1,57 @@
-use crate::solution::Day;
+use crate::error::{SolveError, parse};
+use crate::schema::{Schema, Token};
+use crate::solution::{Day, SolveResult};
 
 const MULT_CHAR: char = '*';
 const ADD_CHAR: char = '+';
 
-fn parse_input(input: &str) -> (Vec<Vec<u64>>, Vec<char>) {
+fn parse_input(input: &str) -> Result<(Vec<Vec<u64>>, Vec<char>), SolveError> {
     let raw_lines: Vec<_> = input.lines().map(|line| line.trim()).collect();
     let (operators_line, raw_num_lines) = raw_lines
         .split_last()
-        .expect("Invalid input, could not split");
+        .ok_or_else(|| SolveError::new("input is empty"))?;
 
     let num_rows: Vec<Vec<u64>> = raw_num_lines
         .iter()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|num| num.parse().expect("Invalid number"))
-                .collect()
-        })
-        .collect();
+        .map(|line| line.split_whitespace().map(parse).collect())
+        .collect::<Result<_, SolveError>>()?;
 
-    // Transpose rows to columns
-    let col_count = num_rows[0].len();
+    // Transpose rows to columns, all rows need the same number of columns
+    let col_count = num_rows.first().map_or(0, |row| row.len());
+    if let Some((line, row)) = raw_num_lines
+        .iter()
+        .zip(&num_rows)
+        .find(|(_, row)| row.len() != col_count)
+    {
+        return Err(SolveError::at(
+            line,
+            format!("expected {col_count} numbers, found {}", row.len()),
+        ));
+    }
     let columns: Vec<Vec<u64>> = (0..col_count)
         .map(|col_idx| num_rows.iter().map(|row| row[col_idx]).collect())
         .collect();
 
     let operators: Vec<char> = operators_line
         .split_whitespace()
-        .map(|s| s.chars().next().expect("Empty operator"))
-        .collect();
+        .map(|s| match s {
+            "*" => Ok(MULT_CHAR),
+            "+" => Ok(ADD_CHAR),
+            _ => Err(SolveError::at(s, format!("unknown operator {s:?}"))),
+        })
+        .collect::<Result<_, SolveError>>()?;
 
-    (columns, operators)
+    Ok((columns, operators))
 }
 
 /// Returns a Vec of problems where each problem is a sequence of numbers
 /// (each column read top-to-bottom is one number) and a Vec of operators.
 /// Columns are read right-to-left.
-fn parse_input_rtl(input: &str) -> (Vec<Vec<u64>>, Vec<char>) {
+fn parse_input_rtl(input: &str) -> Result<(Vec<Vec<u64>>, Vec<char>), SolveError> {
     let raw_lines: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
     let (op_line, num_lines) = raw_lines
         .split_last()
-        .expect("Invalid input, could not split");
+        .ok_or_else(|| SolveError::new("input is empty"))?;
 
     let max_len = raw_lines.iter().map(|line| line.len()).max().unwrap_or(0);
 
@@ -77,7 +89,7 @@ fn parse_input_rtl(input: &str) -> (Vec<Vec<u64>>, Vec<char>) {
         }
     }
 
-    (problems, operators)
+    Ok((problems, operators))
 }
 
 /// Given a slice of columns, each column is a Vec of numbers, compute the puzzle
@@ -89,7 +101,7 @@ fn compute_puzzle(columns: &[Vec<u64>], operator_rows: &[char]) -> u64 {
         .map(|(col, op)| match *op {
             MULT_CHAR => col.iter().product::<u64>(),
             ADD_CHAR => col.iter().sum::<u64>(),
-            _ => panic!("Invalid operator"),
+            _ => unreachable!("operators are checked while parsing"),
         })
         .sum()
 }
@@ -97,14 +109,33 @@ fn compute_puzzle(columns: &[Vec<u64>], operator_rows: &[char]) -> u64 {
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
-        let (columns, operator_rows) = parse_input(input);
-        compute_puzzle(&columns, &operator_rows).into()
+    const SCHEMA: Schema = Schema::Trailer {
+        body: &[
+            Token::OptWs,
+            Token::List {
+                item: &[Token::Uint],
+                sep: &[Token::Ws],
+            },
+            Token::OptWs,
+        ],
+        last: &[
+            Token::OptWs,
+            Token::List {
+                item: &[Token::OneOf("*+")],
+                sep: &[Token::Ws],
+            },
+            Token::OptWs,
+        ],
+    };
+
+    fn part1(input: &str) -> SolveResult {
+        let (columns, operator_rows) = parse_input(input)?;
+        Ok(compute_puzzle(&columns, &operator_rows).into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
-        let (columns, operator_rows) = parse_input_rtl(input);
-        compute_puzzle(&columns, &operator_rows).into()
+    fn part2(input: &str) -> SolveResult {
+        let (columns, operator_rows) = parse_input_rtl(input)?;
+        Ok(compute_puzzle(&columns, &operator_rows).into())
     }
 }
 
@@ -120,15 +151,20 @@ mod tests {
 
     #[test]
     fn test_solve() {
-        let (columns, operator_rows) = parse_input(TEST_INPUT);
+        let (columns, operator_rows) = parse_input(TEST_INPUT).unwrap();
         let result = compute_puzzle(&columns, &operator_rows);
         assert_eq!(result, 4277556);
     }
 
     #[test]
     fn test_solve_rtl() {
-        let (columns, operator_rows) = parse_input_rtl(TEST_INPUT);
+        let (columns, operator_rows) = parse_input_rtl(TEST_INPUT).unwrap();
         let result = compute_puzzle(&columns, &operator_rows);
         assert_eq!(result, 3263827);
     }
+
+    #[test]
+    fn test_schema() {
+        assert!(Solution::SCHEMA.validate(TEST_INPUT).is_ok());
+    }
 }
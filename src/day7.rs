@@ -1,48 +1,40 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::{grid::Grid, position::Position, solution::Day};
+use crate::{
+    error::SolveError,
+    grid::Grid,
+    position::Position,
+    schema::Schema,
+    solution::{Day, SolveResult},
+    visualize::{self, Frame},
+};
 
 const START_LOCATION: char = 'S';
 const SPLITTER: char = '^';
 const EMPTY: char = '.';
 const BEAM: char = '|';
 
-/// Visualize the grid with beam positions marked.
-/// Beams are shown as '*', overlaid on the original grid.
-#[allow(dead_code)]
-fn visualize_beams(grid: &Grid<char>, beams: &HashSet<Position>) -> String {
-    let mut result = String::new();
-    for y in 0..grid.height() {
-        for x in 0..grid.width() {
-            let pos = Position::new(x, y);
-            if beams.contains(&pos) {
-                // Show beam, but preserve splitters/start if beam is on them
-                let cell = grid[pos];
-                if cell == SPLITTER || cell == START_LOCATION {
-                    result.push(cell);
-                } else {
-                    result.push(BEAM);
-                }
-            } else {
-                result.push(grid[pos]);
-            }
-        }
-        result.push('\n');
-    }
-    result
+/// Frame of the grid with beam positions marked.
+/// Beams are shown as '|' over empty cells, splitters and the start stay visible.
+fn beams_frame(grid: &Grid<char>, beams: &HashSet<Position>, caption: String) -> Frame {
+    let on_empty = beams
+        .iter()
+        .copied()
+        .filter(|&pos| grid.get_pos(pos) == Some(&EMPTY));
+    Frame::new(grid).caption(caption).highlight(on_empty, BEAM)
 }
 
 fn parse_input(input: &str) -> Grid<char> {
     Grid::from_rows(input.lines().map(|line| line.chars().collect()).collect())
 }
 
-/// State for beam simulation: (current beam positions, split count)
-type BeamState = (HashSet<Position>, usize);
+/// Find the start position 'S' in the first row
+fn find_start(grid: &Grid<char>) -> Result<Position, SolveError> {
+    if grid.height() == 0 {
+        return Err(SolveError::new("input is empty"));
+    }
 
-fn count_beams(grid: &Grid<char>) -> usize {
-    // start position - find 'S' in the first row
-    let init_position: Position = grid
-        .iter_row(0)
+    grid.iter_row(0)
         .enumerate()
         .find_map(|(col, c)| {
             if *c == START_LOCATION {
@@ -51,66 +43,85 @@ fn count_beams(grid: &Grid<char>) -> usize {
                 None
             }
         })
-        .expect("No start location found");
+        .ok_or_else(|| {
+            SolveError::at_position(
+                1,
+                0..grid.width(),
+                format!("no start location {START_LOCATION:?} found in the first row"),
+            )
+        })
+}
+
+/// Error for a splitter in the first column, which would send a beam off the grid
+fn splitter_at_left_edge(pos: Position) -> SolveError {
+    SolveError::at_position(pos.y + 1, 0..1, "splitter at the left edge")
+}
+
+/// State for beam simulation: (current beam positions, split count)
+type BeamState = (HashSet<Position>, usize);
+
+fn count_beams(grid: &Grid<char>) -> Result<usize, SolveError> {
+    let init_position = find_start(grid)?;
 
     let init_state: BeamState = (HashSet::from([init_position]), 0);
 
-    grid.iter_rows()
+    let (beams, splits) = grid
+        .iter_rows()
         .enumerate()
         .skip(1)
         // fold over each row, tracking (current beams, split count)
-        .fold(init_state, |(current, splits), (row_idx, row_iter)| {
-            // visualize current state
-            // eprintln!("Row {} (splits so far: {}):\n{}", row_idx, splits, visualize_beams(grid, &current));
-
-            // find splitters in this row
-            let splitters: HashSet<Position> = row_iter
-                .enumerate()
-                .filter_map(|(col_idx, &c)| {
-                    if c == SPLITTER {
-                        Some(Position::new(col_idx, row_idx))
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+        .try_fold(
+            init_state,
+            |(current, splits), (row_idx, row_iter)| -> Result<_, SolveError> {
+                visualize::emit(|| {
+                    let caption = format!("row {} (splits so far: {})", row_idx, splits);
+                    beams_frame(grid, &current, caption)
+                });
+
+                // find splitters in this row
+                let splitters: HashSet<Position> = row_iter
+                    .enumerate()
+                    .filter_map(|(col_idx, &c)| {
+                        if c == SPLITTER {
+                            Some(Position::new(col_idx, row_idx))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
 
-            // compute next beam positions and count splits
-            let (next, new_splits): (HashSet<Position>, usize) =
-                current
-                    .iter()
-                    .fold((HashSet::new(), 0), |(mut next, split_count), pos| {
+                // compute next beam positions and count splits
+                let (next, new_splits): (HashSet<Position>, usize) = current.iter().try_fold(
+                    (HashSet::new(), 0),
+                    |(mut next, split_count), pos| -> Result<_, SolveError> {
                         let next_pos = pos.down();
                         // if it hits a splitter, split into two beams adjacent to it
                         if splitters.contains(&next_pos) {
-                            next.insert(next_pos.left().expect("splitter at left edge"));
+                            let left = next_pos
+                                .left()
+                                .ok_or_else(|| splitter_at_left_edge(next_pos))?;
+                            next.insert(left);
                             next.insert(next_pos.right());
-                            (next, split_count + 1)
+                            Ok((next, split_count + 1))
                         } else {
                             next.insert(next_pos);
-                            (next, split_count)
+                            Ok((next, split_count))
                         }
-                    });
+                    },
+                )?;
 
-            (next, splits + new_splits)
-        })
-        .1 // return split count
+                Ok((next, splits + new_splits))
+            },
+        )?;
+
+    visualize::emit(|| beams_frame(grid, &beams, format!("done ({} splits)", splits)));
+
+    Ok(splits)
 }
 
 /// Count timelines using position
-fn count_timelines(grid: &Grid<char>) -> usize {
-    // start position - find 'S' in the first row
-    let init_position: Position = grid
-        .iter_row(0)
-        .enumerate()
-        .find_map(|(col, c)| {
-            if *c == START_LOCATION {
-                Some(Position::new(col, 0))
-            } else {
-                None
-            }
-        })
-        .expect("No start location found");
+fn count_timelines(grid: &Grid<char>) -> Result<usize, SolveError> {
+    let init_position = find_start(grid)?;
 
     // Map of position -> number of timelines at that position
     let init_state: HashMap<Position, usize> = HashMap::from([(init_position, 1)]);
@@ -133,46 +144,50 @@ fn count_timelines(grid: &Grid<char>) -> usize {
         })
         .collect();
 
-    (1..grid.height())
+    let timelines = (1..grid.height())
         // fold over each row, tracking position -> timeline count
-        .fold(init_state, |current, row_idx| {
+        .try_fold(init_state, |current, row_idx| -> Result<_, SolveError> {
             let splitters = &splitters_by_row[row_idx];
 
             // compute next positions with counts
             current
                 .iter()
-                .flat_map(|(&pos, &count)| {
+                .map(|(&pos, &count)| -> Result<_, SolveError> {
                     let next_pos = pos.down();
                     // if it hits a splitter, split into two positions
                     if splitters.contains(&next_pos) {
-                        vec![
-                            (next_pos.left().expect("splitter at left edge"), count),
-                            (next_pos.right(), count),
-                        ]
+                        let left = next_pos
+                            .left()
+                            .ok_or_else(|| splitter_at_left_edge(next_pos))?;
+                        Ok(vec![(left, count), (next_pos.right(), count)])
                     } else {
-                        vec![(next_pos, count)]
+                        Ok(vec![(next_pos, count)])
                     }
                 })
-                .fold(HashMap::new(), |mut acc, (pos, count)| {
-                    *acc.entry(pos).or_insert(0) += count;
-                    acc
+                .try_fold(HashMap::new(), |mut acc, next| {
+                    for (pos, count) in next? {
+                        *acc.entry(pos).or_insert(0) += count;
+                    }
+                    Ok(acc)
                 })
-        })
-        .values()
-        .sum()
+        })?;
+
+    Ok(timelines.values().sum())
 }
 
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
+    const SCHEMA: Schema = Schema::Grid(".S^");
+
+    fn part1(input: &str) -> SolveResult {
         let grid = parse_input(input);
-        count_beams(&grid).into()
+        Ok(count_beams(&grid)?.into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
+    fn part2(input: &str) -> SolveResult {
         let grid = parse_input(input);
-        count_timelines(&grid).into()
+        Ok(count_timelines(&grid)?.into())
     }
 }
 
@@ -201,12 +216,32 @@ mod tests {
     #[test]
     fn test_input() {
         let grid = parse_input(TEST_INPUT);
-        assert_eq!(count_beams(&grid), 21);
+        assert_eq!(count_beams(&grid).unwrap(), 21);
     }
 
     #[test]
     fn test_input_2() {
         let grid = parse_input(TEST_INPUT);
-        assert_eq!(count_timelines(&grid), 40);
+        assert_eq!(count_timelines(&grid).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let grid = parse_input(TEST_INPUT);
+        let (splits, frames) = visualize::capture(|| count_beams(&grid));
+        assert_eq!(splits.unwrap(), 21);
+        // one frame per row, then the final beams
+        assert_eq!(frames.len(), grid.height());
+        assert_eq!(frames.last().unwrap().caption, "done (21 splits)");
+        // the start stays visible under the first beam
+        assert_eq!(
+            frames[0].to_string().lines().nth(1),
+            Some(".......S.......")
+        );
+    }
+
+    #[test]
+    fn test_schema() {
+        assert!(Solution::SCHEMA.validate(TEST_INPUT).is_ok());
     }
 }
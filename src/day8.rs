@@ -1,4 +1,6 @@
-use crate::solution::Day;
+use crate::error::{SolveError, parse};
+use crate::schema::{Schema, Token};
+use crate::solution::{Day, SolveResult};
 use rayon::prelude::*;
 
 /// x,y,z coordinates
@@ -16,15 +18,20 @@ impl std::fmt::Display for Coord3D {
 }
 
 impl std::str::FromStr for Coord3D {
-    type Err = std::num::ParseIntError;
+    type Err = SolveError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(',');
-        Ok(Self::new(
-            parts.next().unwrap().parse()?,
-            parts.next().unwrap().parse()?,
-            parts.next().unwrap().parse()?,
-        ))
+        let parts: Vec<&str> = s.split(',').collect();
+        match parts[..] {
+            [x, y, z] => Ok(Self::new(parse(x)?, parse(y)?, parse(z)?)),
+            _ => Err(SolveError::at(
+                s,
+                format!(
+                    "expected 3 comma-separated coordinates, found {}",
+                    parts.len()
+                ),
+            )),
+        }
     }
 }
 
@@ -45,8 +52,8 @@ impl Coord3D {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Coord3D> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+fn parse_input(input: &str) -> Result<Vec<Coord3D>, SolveError> {
+    input.lines().map(str::parse).collect()
 }
 
 struct DisjointSet {
@@ -192,14 +199,22 @@ fn solve_all(boxes: &[Coord3D]) -> i64 {
 pub struct Solution;
 
 impl Day for Solution {
-    fn part1(input: &str) -> crate::solution::Solution {
-        let coords = parse_input(input);
-        solve(&coords, 1000).into()
+    const SCHEMA: Schema = Schema::Lines(&[
+        Token::Int,
+        Token::Lit(","),
+        Token::Int,
+        Token::Lit(","),
+        Token::Int,
+    ]);
+
+    fn part1(input: &str) -> SolveResult {
+        let coords = parse_input(input)?;
+        Ok(solve(&coords, 1000).into())
     }
 
-    fn part2(input: &str) -> crate::solution::Solution {
-        let coords = parse_input(input);
-        solve_all(&coords).into()
+    fn part2(input: &str) -> SolveResult {
+        let coords = parse_input(input)?;
+        Ok(solve_all(&coords).into())
     }
 }
 
@@ -231,15 +246,31 @@ mod tests {
 
     #[test]
     fn test_solve() {
-        let coords = parse_input(TEST_INPUT);
+        let coords = parse_input(TEST_INPUT).unwrap();
         let result = solve(&coords, 10);
         assert_eq!(result, 40);
     }
 
     #[test]
     fn test_solve_all() {
-        let coords = parse_input(TEST_INPUT);
+        let coords = parse_input(TEST_INPUT).unwrap();
         let result = solve_all(&coords);
         assert_eq!(result, 25272);
     }
+
+    #[test]
+    fn test_missing_coordinate() {
+        let input = "162,817,812\n57,618";
+        let err = parse_input(input).unwrap_err().locate(input);
+        assert_eq!(
+            err.message(),
+            "expected 3 comma-separated coordinates, found 2"
+        );
+        assert_eq!(err.location().unwrap().columns, 0..6);
+    }
+
+    #[test]
+    fn test_schema() {
+        assert!(Solution::SCHEMA.validate(TEST_INPUT).is_ok());
+    }
 }
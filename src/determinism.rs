@@ -0,0 +1,295 @@
+//! Detecting order-dependent answers: each part is solved repeatedly on fresh rayon pools of
+//! different sizes, and the parts whose answers vary between runs are reported.
+//!
+//! Every run happens on the threads of a new pool. std's `HashMap` and `HashSet` seed their
+//! hashers from per-thread random keys, so each run iterates them in a different order.
+
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::runner;
+use crate::select::DaySet;
+
+/// What one run of a part produced: its answer, or the error it failed with
+pub type Answer = Result<String, String>;
+
+/// One run of a part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// Size of the rayon pool the part ran on
+    pub threads: usize,
+    pub answer: Answer,
+}
+
+/// The runs of one part of a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    pub part: u8,
+    pub runs: Vec<Run>,
+}
+
+impl PartReport {
+    /// Distinct answers in the order they first appeared, with the thread counts of the runs
+    /// that gave them
+    pub fn answers(&self) -> Vec<(&Answer, Vec<usize>)> {
+        let mut answers: Vec<(&Answer, Vec<usize>)> = Vec::new();
+        for run in &self.runs {
+            match answers
+                .iter_mut()
+                .find(|(answer, _)| **answer == run.answer)
+            {
+                Some((_, threads)) => threads.push(run.threads),
+                None => answers.push((&run.answer, vec![run.threads])),
+            }
+        }
+        answers
+    }
+
+    /// Whether the runs disagree, including runs that failed while others didn't
+    pub fn varies(&self) -> bool {
+        self.runs
+            .iter()
+            .any(|run| run.answer != self.runs[0].answer)
+    }
+}
+
+/// Largest pool tried on machines with fewer cores, so that they still run the parallel
+/// paths with several threads
+const MIN_MAX_THREADS: usize = 4;
+
+/// Size of the largest pool: one thread per core, and at least [`MIN_MAX_THREADS`]
+pub fn max_threads() -> usize {
+    thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .max(MIN_MAX_THREADS)
+}
+
+/// Pool size of the `run`th run: the largest pool first, then 1, 2, ... up to it again
+pub fn threads(run: usize, max: usize) -> usize {
+    match run % max {
+        0 => max,
+        threads => threads,
+    }
+}
+
+/// Solve each part of `day` `runs` times on `input`, or only `part` if given
+pub fn check(day: u8, input: &str, part: Option<u8>, runs: usize) -> Vec<PartReport> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    parts
+        .into_iter()
+        .map(|part| {
+            check_part(part, runs, || {
+                let result = runner::solve(day, input, Some(part));
+                result.answer(part).map(|sol| sol.to_string())
+            })
+        })
+        .collect()
+}
+
+/// The checks of several days, as run by `--determinism`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub runs: usize,
+    /// Size of the largest pool the parts ran on
+    pub max_threads: usize,
+    pub days: Vec<(u8, Vec<PartReport>)>,
+}
+
+impl Report {
+    /// Check each part (or only `part`) of `days` (with their inputs) `runs` times
+    pub fn run(days: &[(u8, String)], part: Option<u8>, runs: usize) -> Self {
+        let days = days
+            .iter()
+            .map(|(day, input)| (*day, check(*day, input, part, runs)))
+            .collect();
+        Self {
+            runs,
+            max_threads: max_threads(),
+            days,
+        }
+    }
+
+    /// Parts whose answers vary, as `day.part`
+    pub fn varying(&self) -> Vec<String> {
+        self.days
+            .iter()
+            .flat_map(|(day, reports)| {
+                reports
+                    .iter()
+                    .filter(|report| report.varies())
+                    .map(move |report| format!("{}.{}", day, report.part))
+            })
+            .collect()
+    }
+
+    /// Whether every part gave the same answer on every run
+    pub fn stable(&self) -> bool {
+        self.varying().is_empty()
+    }
+
+    /// The report as printed by `--determinism`: each part's answer, or its differing answers
+    /// with the pool sizes that gave them, the `skipped` days without an input, and the
+    /// parts that vary
+    pub fn render(&self, skipped: &[u8]) -> String {
+        let mut text = format!(
+            "=== Determinism: {} runs per part on 1-{} threads ===\n",
+            self.runs, self.max_threads
+        );
+        let show = |answer: &Answer| match answer {
+            Ok(answer) => answer.clone(),
+            Err(message) => format!("error: {}", message),
+        };
+        for (day, reports) in &self.days {
+            text += &format!("\n=== Day {:02} ===\n", day);
+            for report in reports {
+                let answers = report.answers();
+                if !report.varies() {
+                    text += &format!("  · Part {}: stable, {}\n", report.part, show(answers[0].0));
+                    continue;
+                }
+                text += &format!(
+                    "  · Part {}: VARIES, {} different answers\n",
+                    report.part,
+                    answers.len()
+                );
+                for (answer, threads) in answers {
+                    let threads: Vec<String> = threads.iter().map(|t| t.to_string()).collect();
+                    text += &format!(
+                        "      {}  (threads: {})\n",
+                        show(answer),
+                        threads.join(", ")
+                    );
+                }
+            }
+        }
+        if !skipped.is_empty() {
+            let skipped: DaySet = skipped.iter().copied().collect();
+            text += &format!("\n  Skipped (no input): {}\n", skipped);
+        }
+
+        let varying = self.varying();
+        if varying.is_empty() {
+            text += "\n  All parts gave the same answer on every run\n";
+        } else {
+            text += &format!("\n  Nondeterministic parts: {}\n", varying.join(", "));
+        }
+        text
+    }
+}
+
+fn check_part(part: u8, runs: usize, solve: impl Fn() -> Answer + Sync) -> PartReport {
+    let max = max_threads();
+    let runs = (0..runs)
+        .map(|run| {
+            let threads = threads(run, max);
+            let answer = match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+                Ok(pool) => pool.install(&solve),
+                Err(err) => Err(format!("cannot start {} threads: {}", threads, err)),
+            };
+            Run { threads, answer }
+        })
+        .collect();
+    PartReport { part, runs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn test_threads() {
+        let counts: Vec<usize> = (0..6).map(|run| threads(run, 4)).collect();
+        assert_eq!(counts, [4, 1, 2, 3, 4, 1]);
+        assert_eq!(threads(3, 1), 1);
+    }
+
+    #[test]
+    fn test_check() {
+        let reports = check(1, DAY1_INPUT, None, 3);
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|report| !report.varies()));
+        let answers = reports[1].answers();
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].0, &Ok("6".into()));
+        assert_eq!(answers[0].1.len(), 3);
+
+        let invalid = check(1, "X1", Some(1), 2);
+        assert_eq!(invalid.len(), 1);
+        assert!(!invalid[0].varies());
+        assert_eq!(invalid[0].runs[0].answer, Err("invalid input".into()));
+    }
+
+    #[test]
+    fn test_render() {
+        let run = |threads, answer: &str| Run {
+            threads,
+            answer: Ok(answer.into()),
+        };
+        let report = Report {
+            runs: 3,
+            max_threads: 4,
+            days: vec![(
+                1,
+                vec![
+                    PartReport {
+                        part: 1,
+                        runs: vec![run(4, "3"), run(1, "3"), run(2, "3")],
+                    },
+                    PartReport {
+                        part: 2,
+                        runs: vec![run(4, "6"), run(1, "7"), run(2, "6")],
+                    },
+                ],
+            )],
+        };
+        let expected = "\
+=== Determinism: 3 runs per part on 1-4 threads ===
+
+=== Day 01 ===
+  · Part 1: stable, 3
+  · Part 2: VARIES, 2 different answers
+      6  (threads: 4, 2)
+      7  (threads: 1)
+
+  Skipped (no input): 2
+
+  Nondeterministic parts: 1.2
+";
+        assert_eq!(report.render(&[2]), expected);
+        assert!(!report.stable());
+    }
+
+    #[test]
+    fn test_varies() {
+        let report = check_part(1, 2, || Ok(rayon::current_num_threads().to_string()));
+        assert!(report.varies());
+        assert_eq!(report.runs[1].answer, Ok("1".into()));
+
+        let report = PartReport {
+            part: 2,
+            runs: vec![
+                Run {
+                    threads: 4,
+                    answer: Ok("1".into()),
+                },
+                Run {
+                    threads: 1,
+                    answer: Err("boom".into()),
+                },
+                Run {
+                    threads: 2,
+                    answer: Ok("1".into()),
+                },
+            ],
+        };
+        assert!(report.varies());
+        let answers = report.answers();
+        assert_eq!(answers[0], (&Ok("1".into()), vec![4, 2]));
+        assert_eq!(answers[1], (&Err("boom".into()), vec![1]));
+    }
+}
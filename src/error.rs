@@ -0,0 +1,414 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::Once;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema::Violation;
+
+/// Where in the input an error happened, before it has been resolved against the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum Span {
+    /// Memory address range of a `&str` fragment borrowed from the input.
+    /// Parsers usually only hold slices of the input, so this lets them point at the
+    /// offending text without threading line numbers around.
+    Fragment(Range<usize>),
+    /// 1-based line with 0-based character columns, for callers that already know them
+    Position { line: usize, columns: Range<usize> },
+}
+
+/// Resolved location of an error in the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    /// 1-based line number
+    pub line: usize,
+    /// 0-based character columns within the line
+    pub columns: Range<usize>,
+    /// Full text of the offending line
+    pub text: String,
+}
+
+/// Error returned by a solver, optionally pointing at the offending part of the input.
+/// Boxed so that results stay small on the happy path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolveError(Box<Inner>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Inner {
+    message: String,
+    /// Extra context, innermost first
+    context: Vec<String>,
+    span: Option<Span>,
+    location: Option<Location>,
+}
+
+impl SolveError {
+    /// Create an error that is not tied to a location in the input.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(Box::new(Inner {
+            message: message.into(),
+            context: Vec::new(),
+            span: None,
+            location: None,
+        }))
+    }
+
+    /// Create an error pointing at `fragment`, which must be a slice of the solver's input
+    /// for the location to be resolved by [`SolveError::locate`].
+    pub fn at(fragment: &str, message: impl Into<String>) -> Self {
+        let start = fragment.as_ptr() as usize;
+        let mut err = Self::new(message);
+        err.0.span = Some(Span::Fragment(start..start + fragment.len()));
+        err
+    }
+
+    /// Create an error at a known 1-based line and 0-based character columns.
+    pub fn at_position(line: usize, columns: Range<usize>, message: impl Into<String>) -> Self {
+        let mut err = Self::new(message);
+        err.0.span = Some(Span::Position { line, columns });
+        err
+    }
+
+    /// Add context describing what was being done when the error happened.
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        self.0.context.push(context.into());
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.0.message
+    }
+
+    /// Location in the input, available once the error has been resolved with `locate`.
+    pub fn location(&self) -> Option<&Location> {
+        self.0.location.as_ref()
+    }
+
+    /// Resolve the span against the input the solver was given, so it can be rendered
+    /// with the offending line. Spans that don't fall within the input are dropped.
+    pub fn locate(mut self, input: &str) -> Self {
+        if let Some(location) = self.0.span.take().and_then(|span| resolve(span, input)) {
+            self.0.location = Some(location);
+        }
+        self
+    }
+}
+
+fn resolve(span: Span, input: &str) -> Option<Location> {
+    match span {
+        Span::Fragment(range) => {
+            let base = input.as_ptr() as usize;
+            (range.start >= base && range.end <= base + input.len())
+                .then(|| locate_bytes(input, range.start - base..range.end - base))
+        }
+        Span::Position { line, columns } => {
+            let text = input.lines().nth(line.checked_sub(1)?)?;
+            Some(Location {
+                line,
+                columns,
+                text: text.to_string(),
+            })
+        }
+    }
+}
+
+/// Turn a byte range within the input into a line and character columns
+fn locate_bytes(input: &str, range: Range<usize>) -> Location {
+    let line_start = input[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[line_start..]
+        .find('\n')
+        .map_or(input.len(), |i| line_start + i);
+    let text = input[line_start..line_end].trim_end_matches('\r');
+
+    let line = input[..line_start].matches('\n').count() + 1;
+    let start = input[line_start..range.start].chars().count();
+    let end = start + input[range.start..range.end.min(line_end)].chars().count();
+
+    Location {
+        line,
+        columns: start..end,
+        text: text.to_string(),
+    }
+}
+
+impl From<&Violation> for SolveError {
+    fn from(violation: &Violation) -> Self {
+        Self::at_position(
+            violation.line,
+            violation.column - 1..violation.column,
+            violation.message.clone(),
+        )
+    }
+}
+
+/// Renders the error with the offending line quoted and a caret under the bad columns:
+///
+/// ```text
+/// error: unknown rotation 'X', expected 'L' or 'R'
+///  --> line 3, column 1
+///   |
+/// 3 | X48
+///   | ^
+///   = while applying command "X48"
+/// ```
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Inner {
+            message,
+            context,
+            location,
+            ..
+        } = &*self.0;
+        write!(f, "error: {message}")?;
+
+        let gutter = location
+            .as_ref()
+            .map_or(1, |loc| loc.line.to_string().len());
+        let pad = " ".repeat(gutter);
+
+        if let Some(loc) = location {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{pad}--> line {}, column {}",
+                loc.line,
+                loc.columns.start + 1
+            )?;
+            writeln!(f, "{pad} |")?;
+            writeln!(f, "{} | {}", loc.line, loc.text)?;
+            write!(
+                f,
+                "{pad} | {}{}",
+                " ".repeat(loc.columns.start),
+                "^".repeat(loc.columns.len().max(1))
+            )?;
+        }
+
+        for context in context {
+            write!(f, "\n{pad} = {context}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Extension trait to add context to fallible results.
+pub trait Context<T> {
+    fn context(self, context: impl Into<String>) -> Result<T, SolveError>;
+}
+
+impl<T> Context<T> for Result<T, SolveError> {
+    fn context(self, context: impl Into<String>) -> Result<T, SolveError> {
+        self.map_err(|err| err.context(context))
+    }
+}
+
+/// Parse an input fragment, pointing the error at the fragment if it fails.
+pub fn parse<T>(fragment: &str) -> Result<T, SolveError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fragment
+        .parse()
+        .map_err(|err| SolveError::at(fragment, format!("cannot parse {fragment:?}: {err}")))
+}
+
+thread_local! {
+    /// Depth of `catch_panic` calls in progress on this thread, the panic hook stays quiet
+    /// for this thread while non-zero
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Message and location of the last panic on this thread caught by the hook
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+static HOOK: Once = Once::new();
+
+/// Message of a panic payload, which is a `&str` or a `String` for `panic!` with a message
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn install_panic_hook() {
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // panics on other threads, even during a catch here, are reported as usual
+            if CATCHING.with(Cell::get) == 0 {
+                return default_hook(info);
+            }
+
+            let message = payload_message(info.payload());
+            let message = match info.location() {
+                Some(loc) => format!(
+                    "{message}\n  at {}:{}:{}",
+                    loc.file(),
+                    loc.line(),
+                    loc.column()
+                ),
+                None => message,
+            };
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+/// Run a solver, turning a panic into a `SolveError` so the runner can carry on with the
+/// other days. Arithmetic overflow (see the `checked` profile) gets a hint to switch types.
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T, SolveError>) -> Result<T, SolveError> {
+    install_panic_hook();
+    CATCHING.with(|catching| catching.set(catching.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|catching| catching.set(catching.get() - 1));
+
+    result.unwrap_or_else(|payload| {
+        // a panic on a worker thread (rayon) reaches us only as its payload, with no location
+        let panic = LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_else(|| payload_message(payload.as_ref()));
+        let (message, location) = panic.split_once('\n').unwrap_or((&panic, ""));
+
+        let err = if message.contains("with overflow") {
+            SolveError::new(format!("arithmetic overflow: {message}")).context(
+                "use a wider integer type or bigint::BigInt (Solution::Big) for this value",
+            )
+        } else {
+            SolveError::new(format!("solver panicked: {message}"))
+        };
+        match location.trim() {
+            "" => Err(err),
+            location => Err(err.context(location)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_fragment() {
+        let input = "L68\nR30\nX48\n";
+        let err = SolveError::at(&input[8..9], "unknown rotation").locate(input);
+        let loc = err.location().unwrap();
+        assert_eq!(loc.line, 3);
+        assert_eq!(loc.columns, 0..1);
+        assert_eq!(loc.text, "X48");
+    }
+
+    #[test]
+    fn test_locate_foreign_fragment() {
+        // fragments that aren't part of the input can't be located
+        let err = SolveError::at("X48", "unknown rotation").locate("L68\nR30");
+        assert_eq!(err.location(), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "3-5\n10-1x";
+        let err = parse::<u64>(&input[7..9]).unwrap_err().locate(input);
+        assert_eq!(
+            err.message(),
+            "cannot parse \"1x\": invalid digit found in string"
+        );
+        assert_eq!(err.location().unwrap().columns, 3..5);
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "L68\nR30\nX48";
+        let err = SolveError::at(&input[8..9], "unknown rotation 'X', expected 'L' or 'R'")
+            .context("while applying command \"X48\"")
+            .locate(input);
+        assert_eq!(
+            err.to_string(),
+            "\
+error: unknown rotation 'X', expected 'L' or 'R'
+ --> line 3, column 1
+  |
+3 | X48
+  | ^
+  = while applying command \"X48\""
+        );
+    }
+
+    #[test]
+    fn test_render_without_location() {
+        let err = SolveError::new("no start location found").context("while parsing grid");
+        assert_eq!(
+            err.to_string(),
+            "error: no start location found\n  = while parsing grid"
+        );
+    }
+
+    #[test]
+    fn test_from_violation() {
+        let violation = Violation {
+            line: 2,
+            column: 4,
+            message: "expected digit".to_string(),
+        };
+        let err = SolveError::from(&violation).locate("1-2\n10-x");
+        let loc = err.location().unwrap();
+        assert_eq!((loc.line, loc.columns.clone()), (2, 3..4));
+        assert_eq!(loc.text, "10-x");
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        let err = catch_panic::<()>(|| panic!("Invalid value")).unwrap_err();
+        assert_eq!(err.message(), "solver panicked: Invalid value");
+        assert!(err.to_string().contains("at src/error.rs:"));
+
+        assert_eq!(catch_panic(|| Ok(7)), Ok(7));
+    }
+
+    #[test]
+    fn test_concurrent_catches() {
+        // each thread gets its own message back, however the panics interleave
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    (0..50)
+                        .map(|_| catch_panic::<()>(|| panic!("thread {i}")).unwrap_err())
+                        .all(|err| err.message() == format!("solver panicked: thread {i}"))
+                })
+            })
+            .collect();
+        assert!(threads.into_iter().all(|thread| thread.join().unwrap()));
+
+        // a panic on a worker thread only comes back as its payload
+        let err = catch_panic::<()>(|| {
+            std::thread::scope(|scope| match scope.spawn(|| panic!("worker")).join() {
+                Ok(()) => Ok(()),
+                Err(payload) => panic::resume_unwind(payload),
+            })
+        })
+        .unwrap_err();
+        assert_eq!(err.message(), "solver panicked: worker");
+    }
+
+    #[test]
+    // overflow checks are tied to debug assertions unless using the `checked` profile
+    #[cfg_attr(not(debug_assertions), ignore)]
+    fn test_catch_overflow() {
+        let err = catch_panic(|| {
+            let x: u8 = std::hint::black_box(255);
+            Ok(x + 1)
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "arithmetic overflow: attempt to add with overflow"
+        );
+    }
+}
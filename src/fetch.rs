@@ -0,0 +1,252 @@
+//! Fetching pages from the Advent of Code site, or a stand-in for it. Plain `http://` URLs are
+//! fetched directly, which is enough for a local server; `https://` goes through `curl`, since
+//! the crate has no TLS. The session cookie comes from `AOC_SESSION` and is only ever sent to
+//! [`SITE`], so a mistyped or borrowed URL can't leak it.
+//!
+//! Fetched pages are cached in files so that repeated runs don't hit the site.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+/// Environment variable holding the session cookie of a logged-in browser
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The only origin the session cookie is sent to
+pub const SITE: &str = "https://adventofcode.com";
+
+/// Time allowed for connecting and each read of a plain HTTP fetch
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The session cookie, if set
+pub fn session() -> Option<String> {
+    std::env::var(SESSION_VAR)
+        .ok()
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// GET `url` and return the body, failing on any status but 200. `session` is dropped unless
+/// `url` is on [`SITE`].
+pub fn get(url: &str, session: Option<&str>) -> Result<String, String> {
+    let session = session.filter(|_| on_site(url));
+    if let Some(rest) = url.strip_prefix("http://") {
+        get_http(rest, session)
+    } else if url.starts_with("https://") {
+        get_curl(url, session)
+    } else {
+        Err(format!(
+            "unsupported URL {}, expected http:// or https://",
+            url
+        ))
+    }
+}
+
+/// Whether `url` is on [`SITE`] itself, not merely a host whose name starts the same
+fn on_site(url: &str) -> bool {
+    url.strip_prefix(SITE)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Plain HTTP/1.0 GET, so the response is neither chunked nor kept alive
+fn get_http(rest: &str, session: Option<&str>) -> Result<String, String> {
+    let (host, path) = match rest.find('/') {
+        Some(slash) => rest.split_at(slash),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+    let error = |err: std::io::Error| format!("cannot fetch http://{}: {}", rest, err);
+
+    let mut stream = TcpStream::connect(&address).map_err(error)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(error)?;
+    let mut request = format!("GET {} HTTP/1.0\r\nHost: {}\r\n", path, host);
+    if let Some(session) = session {
+        request += &format!("Cookie: session={}\r\n", session);
+    }
+    request += "\r\n";
+    stream.write_all(request.as_bytes()).map_err(error)?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).map_err(error)?;
+
+    let response = String::from_utf8_lossy(&response);
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| format!("malformed response from http://{}", rest))?;
+    let status = head.lines().next().unwrap_or_default();
+    match status.split_whitespace().nth(1) {
+        Some("200") => Ok(body.to_string()),
+        _ => Err(format!("http://{} answered {}", rest, status)),
+    }
+}
+
+/// GET through curl. The session cookie goes in a config read from stdin, as arguments are
+/// visible to every user in `ps`.
+fn get_curl(url: &str, session: Option<&str>) -> Result<String, String> {
+    let error = |err: std::io::Error| format!("cannot run curl for {}: {}", url, err);
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--config", "-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(error)?;
+    // small enough for the pipe's buffer, so writing before reading the output can't block
+    let mut stdin = curl.stdin.take().expect("stdin is piped");
+    stdin
+        .write_all(curl_config(session).as_bytes())
+        .map_err(error)?;
+    drop(stdin);
+    let output = curl.wait_with_output().map_err(error)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("cannot fetch {}: {}", url, stderr.trim()));
+    }
+    String::from_utf8(output.stdout).map_err(|_| format!("{} is not UTF-8", url))
+}
+
+/// curl config setting the session cookie, if any
+fn curl_config(session: Option<&str>) -> String {
+    match session {
+        Some(session) => {
+            let quoted = session.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("cookie = \"session={}\"\n", quoted)
+        }
+        None => String::new(),
+    }
+}
+
+/// Time since the cached copy at `path` was written, None if there is none
+pub fn age(path: &Path) -> Option<Duration> {
+    let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+    SystemTime::now().duration_since(modified.ok()?).ok()
+}
+
+/// The cached copy at `path` if it is younger than `max_age` (None: any age) and not
+/// `refresh`ing, else what `fetch` returns, which is then cached
+pub fn cached(
+    path: &Path,
+    max_age: Option<Duration>,
+    refresh: bool,
+    fetch: impl FnOnce() -> Result<String, String>,
+) -> Result<String, String> {
+    let fresh = age(path).is_some_and(|age| max_age.is_none_or(|max_age| age < max_age));
+    if fresh
+        && !refresh
+        && let Ok(text) = fs::read_to_string(path)
+    {
+        return Ok(text);
+    }
+
+    let text = fetch()?;
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    fs::write(path, &text).map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve one request with `response`, returning the request's header lines
+    fn serve_once(response: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let head: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect();
+            stream.write_all(response.as_bytes()).unwrap();
+            head
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn test_get_http() {
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}");
+        assert_eq!(
+            get(&format!("{}/x/y.json", url), Some("abc")),
+            Ok("{}".into())
+        );
+        let head = server.join().unwrap();
+        assert_eq!(head[0], "GET /x/y.json HTTP/1.0");
+        assert!(
+            !head.iter().any(|line| line.starts_with("Cookie")),
+            "{head:?}"
+        );
+
+        let (url, server) = serve_once("HTTP/1.1 404 Not Found\r\n\r\n");
+        assert!(get(&url, None).unwrap_err().contains("404"));
+        server.join().unwrap();
+        assert!(get("ftp://example.com", None).is_err());
+    }
+
+    #[test]
+    fn test_get_curl() {
+        assert_eq!(curl_config(None), "");
+        assert_eq!(
+            curl_config(Some(r#"a"b\"#)),
+            "cookie = \"session=a\\\"b\\\\\"\n"
+        );
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}");
+        // straight to curl, as `get` would drop the session for a local server
+        assert_eq!(get_curl(&url, Some("abc")), Ok("{}".into()));
+        let head = server.join().unwrap();
+        assert!(
+            head.contains(&"Cookie: session=abc".to_string()),
+            "{head:?}"
+        );
+    }
+
+    #[test]
+    fn test_on_site() {
+        assert!(on_site("https://adventofcode.com"));
+        assert!(on_site("https://adventofcode.com/2025/day/1"));
+        assert!(!on_site("http://adventofcode.com/2025/day/1"));
+        assert!(!on_site("https://adventofcode.com.evil.example/"));
+        assert!(!on_site("https://adventofcode.com@evil.example/"));
+        assert!(!on_site("https://example.com/?https://adventofcode.com"));
+    }
+
+    #[test]
+    fn test_cached() {
+        let path = std::env::temp_dir().join(format!("fetch-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(
+            cached(&path, None, false, || Ok("a".into())),
+            Ok("a".into())
+        );
+        assert_eq!(
+            cached(&path, None, false, || Ok("b".into())),
+            Ok("a".into())
+        );
+        assert_eq!(cached(&path, None, true, || Ok("b".into())), Ok("b".into()));
+        assert!(age(&path).unwrap() < Duration::from_secs(60));
+        let expired = Some(Duration::ZERO);
+        assert_eq!(
+            cached(&path, expired, false, || Err("x".into())),
+            Err("x".into())
+        );
+        fs::remove_file(&path).unwrap();
+        assert_eq!(age(&path), None);
+    }
+}
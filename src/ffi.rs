@@ -0,0 +1,218 @@
+//! C API over the registry and runner, for calling the solutions from C/C++ harnesses.
+//! `include/advent_2025.h` is generated from this module by the build script.
+//!
+//! Answers and error messages are returned as NUL-terminated strings owned by the caller,
+//! released with [`aoc_string_free`]. Panics are caught at the boundary and reported as
+//! [`AocStatus::Panicked`], they never unwind into the caller.
+
+use std::ffi::{CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::days;
+use crate::runner::{self, Outcome};
+
+/// Result of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    /// A required pointer was null
+    NullArgument = 1,
+    /// The day isn't implemented
+    UnknownDay = 2,
+    /// The part isn't 1 or 2
+    InvalidPart = 3,
+    /// The input isn't UTF-8 or doesn't match the day's schema
+    InvalidInput = 4,
+    /// The solver returned an error or panicked
+    Failed = 5,
+    /// Panicked outside the solver, this is a bug
+    Panicked = 6,
+}
+
+/// Number of implemented days
+#[unsafe(no_mangle)]
+pub extern "C" fn aoc_num_days() -> usize {
+    panic::catch_unwind(|| days().len()).unwrap_or(0)
+}
+
+/// Write up to `capacity` implemented day numbers to `out`, in order. Returns the total
+/// number of days, which may exceed `capacity`.
+///
+/// # Safety
+/// `out` must be valid for writing `capacity` bytes, or null if `capacity` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_days(out: *mut u8, capacity: usize) -> usize {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let days = days();
+        if !out.is_null() {
+            for (i, solvers) in days.iter().take(capacity).enumerate() {
+                // SAFETY: i < capacity, guaranteed writable by the caller
+                unsafe { *out.add(i) = solvers.day };
+            }
+        }
+        days.len()
+    }))
+    .unwrap_or(0)
+}
+
+/// Solve `part` (1 or 2) of `day` on `len` bytes of `input`. On success stores the answer
+/// in `*out`, on failure stores the error message, or null if there is none. Strings stored
+/// in `*out` must be released with `aoc_string_free`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    out: *mut *mut c_char,
+) -> AocStatus {
+    if out.is_null() {
+        return AocStatus::NullArgument;
+    }
+    // SAFETY: checked for null, valid for writes per the caller
+    unsafe { *out = ptr::null_mut() };
+    if input.is_null() && len > 0 {
+        return AocStatus::NullArgument;
+    }
+    let bytes = match len {
+        0 => &[][..],
+        // SAFETY: guaranteed by the caller
+        _ => unsafe { std::slice::from_raw_parts(input, len) },
+    };
+
+    let (status, text) = panic::catch_unwind(AssertUnwindSafe(|| solve(day, part, bytes)))
+        .unwrap_or((AocStatus::Panicked, None));
+    if let Some(text) = text {
+        // SAFETY: as above
+        unsafe { *out = into_c_string(text) };
+    }
+    status
+}
+
+fn solve(day: u8, part: u8, input: &[u8]) -> (AocStatus, Option<String>) {
+    if crate::get_day_solvers(day).is_none() {
+        let message = format!("day {} is not implemented", day);
+        return (AocStatus::UnknownDay, Some(message));
+    }
+    if !matches!(part, 1 | 2) {
+        return (
+            AocStatus::InvalidPart,
+            Some(format!("invalid part {}", part)),
+        );
+    }
+    let Ok(input) = std::str::from_utf8(input) else {
+        let message = "input is not valid UTF-8".to_string();
+        return (AocStatus::InvalidInput, Some(message));
+    };
+
+    let result = runner::solve(day, input, Some(part));
+    match &result.outcome {
+        Outcome::Solved { .. } => {
+            let answer = result.part(part).map(|sol| sol.to_string());
+            (AocStatus::Ok, answer)
+        }
+        Outcome::InvalidInput(errors) => {
+            let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+            (AocStatus::InvalidInput, Some(messages.join("\n")))
+        }
+        Outcome::Failed(err) => (AocStatus::Failed, Some(err.to_string())),
+        Outcome::Skipped => unreachable!("the input is always given"),
+    }
+}
+
+fn into_c_string(text: String) -> *mut c_char {
+    CString::new(text.replace('\0', ""))
+        .expect("NUL bytes were removed")
+        .into_raw()
+}
+
+/// Release a string returned by `aoc_solve`. Null is ignored.
+///
+/// # Safety
+/// `text` must be null or a string from `aoc_solve` that wasn't freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    /// Call `aoc_solve`, returning the status and the string taken from `out`
+    fn call(day: u8, part: u8, input: &str) -> (AocStatus, Option<String>) {
+        let mut out = ptr::null_mut();
+        let status = unsafe { aoc_solve(day, part, input.as_ptr(), input.len(), &mut out) };
+        let text = (!out.is_null()).then(|| unsafe {
+            let text = CStr::from_ptr(out).to_str().unwrap().to_string();
+            aoc_string_free(out);
+            text
+        });
+        (status, text)
+    }
+
+    #[test]
+    fn test_days() {
+        let mut days = [0; 2];
+        let count = unsafe { aoc_days(days.as_mut_ptr(), days.len()) };
+        assert_eq!(count, aoc_num_days());
+        assert_eq!(days, [1, 2]);
+        assert_eq!(unsafe { aoc_days(ptr::null_mut(), 0) }, count);
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(call(1, 1, DAY1_INPUT), (AocStatus::Ok, Some("3".into())));
+        assert_eq!(call(1, 2, DAY1_INPUT), (AocStatus::Ok, Some("6".into())));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(call(99, 1, "").0, AocStatus::UnknownDay);
+        assert_eq!(call(1, 3, DAY1_INPUT).0, AocStatus::InvalidPart);
+        let (status, message) = call(1, 1, "L68\nX30");
+        assert_eq!(status, AocStatus::InvalidInput);
+        assert!(message.unwrap().contains("line 2"));
+
+        let bytes = [0xff, 0xfe];
+        let mut out = ptr::null_mut();
+        let status = unsafe { aoc_solve(1, 1, bytes.as_ptr(), bytes.len(), &mut out) };
+        assert_eq!(status, AocStatus::InvalidInput);
+        unsafe { aoc_string_free(out) };
+
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), 0, ptr::null_mut()) };
+        assert_eq!(status, AocStatus::NullArgument);
+    }
+
+    #[test]
+    fn test_header() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/advent_2025.h"));
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/advent_2025.h");
+        if std::env::var_os("UPDATE_HEADER").is_some() {
+            std::fs::write(path, generated).unwrap();
+        }
+        let committed = std::fs::read_to_string(path).unwrap();
+        assert!(
+            committed == generated,
+            "{} is out of date, rerun with UPDATE_HEADER=1",
+            path
+        );
+        // the include guard is the only macro, crate constants stay out of the C API
+        let defines: Vec<&str> = generated
+            .lines()
+            .filter(|line| line.starts_with("#define"))
+            .collect();
+        assert_eq!(defines, ["#define ADVENT_2025_H"]);
+    }
+}
@@ -0,0 +1,240 @@
+//! Seeded generators of synthetic inputs in each day's format, for running the solvers on
+//! inputs larger (or smaller) than the official ones. The same day, scale and seed always
+//! give the same input.
+//!
+//! Scale 1 is about the size of an official input. The scale multiplies the amount of input:
+//! lines, ranges, points or problems, and the cells of grids, whose sides grow with its square
+//! root.
+
+use std::fmt::Write;
+
+/// Seed used when none is given
+pub const DEFAULT_SEED: u64 = 2025;
+
+/// Small deterministic random number generator (SplitMix64), good enough for test inputs and
+/// stable across platforms and releases, unlike hashers
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `low..=high`
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        let span = high - low + 1;
+        low + ((u128::from(self.next_u64()) * u128::from(span)) >> 64) as u64
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < p
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+}
+
+type Generator = fn(&mut Rng, f64) -> String;
+
+/// Generators of the implemented days
+const GENERATORS: &[(u8, Generator)] = &[
+    (1, day1),
+    (2, day2),
+    (3, day3),
+    (4, day4),
+    (5, day5),
+    (6, day6),
+    (7, day7),
+    (8, day8),
+];
+
+/// Days with a generator
+pub fn days() -> impl Iterator<Item = u8> {
+    GENERATORS.iter().map(|&(day, _)| day)
+}
+
+/// Input for `day` at `scale` from `seed`, None if the day has no generator
+pub fn generate(day: u8, scale: f64, seed: u64) -> Option<String> {
+    let &(_, generator) = GENERATORS.iter().find(|&&(d, _)| d == day)?;
+    // different days get unrelated streams from the same seed
+    let mut rng = Rng::new(seed ^ u64::from(day).wrapping_mul(0x2545_f491_4f6c_dd1d));
+    Some(generator(&mut rng, scale))
+}
+
+/// `base` scaled, at least `min`
+fn count(base: usize, scale: f64, min: usize) -> usize {
+    ((base as f64 * scale).round() as usize).max(min)
+}
+
+/// Side of a square grid with `scale` times the cells of a `base` by `base` grid
+fn side(base: usize, scale: f64, min: usize) -> usize {
+    ((base as f64 * scale.sqrt()).round() as usize).max(min)
+}
+
+fn lines(count: usize, mut line: impl FnMut() -> String) -> String {
+    (0..count).map(|_| line() + "\n").collect()
+}
+
+/// Dial rotations like `L68`
+fn day1(rng: &mut Rng, scale: f64) -> String {
+    lines(count(4000, scale, 1), || {
+        format!("{}{}", rng.pick(&['L', 'R']), rng.range(1, 999))
+    })
+}
+
+/// One line of disjoint, increasing ID ranges like `11-22,95-115`
+fn day2(rng: &mut Rng, scale: f64) -> String {
+    let mut start = rng.range(10, 1000);
+    let ranges: Vec<String> = (0..count(35, scale, 1))
+        .map(|_| {
+            let end = start + rng.range(0, 100_000);
+            let range = format!("{}-{}", start, end);
+            start = end + rng.range(1, 1_000_000);
+            range
+        })
+        .collect();
+    ranges.join(",") + "\n"
+}
+
+/// Banks of 100 battery joltages 1-9
+fn day3(rng: &mut Rng, scale: f64) -> String {
+    lines(count(200, scale, 1), || {
+        (0..100)
+            .map(|_| char::from(b'0' + rng.range(1, 9) as u8))
+            .collect()
+    })
+}
+
+/// Grid of paper rolls `@` and free cells `.`
+fn day4(rng: &mut Rng, scale: f64) -> String {
+    let side = side(140, scale, 3);
+    lines(side, || {
+        (0..side)
+            .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+            .collect()
+    })
+}
+
+/// Fresh ID ranges, a blank line, then available IDs
+fn day5(rng: &mut Rng, scale: f64) -> String {
+    const MAX_ID: u64 = 500_000_000_000_000;
+    let ranges = lines(count(180, scale, 1), || {
+        let start = rng.range(1, MAX_ID);
+        format!("{}-{}", start, start + rng.range(0, MAX_ID / 100))
+    });
+    let ids = lines(count(1000, scale, 1), || rng.range(1, MAX_ID).to_string());
+    format!("{}\n{}", ranges, ids)
+}
+
+/// Worksheet of problems: columns of up to 3-digit numbers over 4 rows, each problem
+/// aligned left or right, with its operator under its first column
+fn day6(rng: &mut Rng, scale: f64) -> String {
+    const ROWS: usize = 4;
+    let mut rows = vec![String::new(); ROWS + 1];
+    for problem in 0..count(1000, scale, 1) {
+        if problem > 0 {
+            rows.iter_mut().for_each(|row| row.push(' '));
+        }
+        let width = rng.range(1, 3) as usize;
+        let left = rng.chance(0.5);
+        for row in &mut rows[..ROWS] {
+            let digits = rng.range(1, width as u64) as u32;
+            let number = rng.range(10u64.pow(digits - 1).max(1), 10u64.pow(digits) - 1);
+            if left {
+                write!(row, "{:<width$}", number).unwrap();
+            } else {
+                write!(row, "{:>width$}", number).unwrap();
+            }
+        }
+        let operator = rng.pick(&['+', '*']);
+        write!(rows[ROWS], "{:<width$}", operator).unwrap();
+    }
+    rows.iter().map(|row| row.clone() + "\n").collect()
+}
+
+/// Manifold with the start `S` in the middle of the top row and splitters `^` on every
+/// other row below it, never next to each other or on the edges. Splitters thin out on tall
+/// grids so that the number of timelines stays within 64 bits.
+fn day7(rng: &mut Rng, scale: f64) -> String {
+    let side = side(141, scale, 5) | 1;
+    let splitter_rows = (side - 2) / 2;
+    let density = (1.0 / 3.0f64).min(32.0 / splitter_rows as f64);
+    let mut grid = vec![vec!['.'; side]; side];
+    grid[0][side / 2] = 'S';
+    for row in grid.iter_mut().skip(2).step_by(2) {
+        for col in 1..side - 1 {
+            if row[col - 1] != '^' && rng.chance(density) {
+                row[col] = '^';
+            }
+        }
+    }
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// Junction box coordinates like `162,817,812`
+fn day8(rng: &mut Rng, scale: f64) -> String {
+    lines(count(1000, scale, 2), || {
+        let mut coordinate = || rng.range(0, 99_999);
+        format!("{},{},{}", coordinate(), coordinate(), coordinate())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_day_solvers;
+    use crate::runner::{self, Outcome};
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(1);
+        let mut again = Rng::new(1);
+        assert_eq!(rng.next_u64(), again.next_u64());
+        assert!((0..1000).all(|_| (5..=7).contains(&rng.range(5, 7))));
+        assert!((0..1000).any(|_| rng.range(5, 7) == 7));
+    }
+
+    #[test]
+    fn test_generate() {
+        for day in days() {
+            let input = generate(day, 0.05, DEFAULT_SEED).unwrap();
+            let schema = get_day_solvers(day).unwrap().schema;
+            assert!(schema.validate(&input).is_ok(), "day {}:\n{}", day, input);
+            let result = runner::solve(day, &input, None);
+            assert!(
+                matches!(result.outcome, Outcome::Solved { .. }),
+                "day {}: {:?}",
+                day,
+                result.outcome
+            );
+        }
+        assert_eq!(generate(25, 1.0, DEFAULT_SEED), None);
+    }
+
+    #[test]
+    fn test_scale_and_seed() {
+        let small = generate(1, 0.1, 7).unwrap();
+        assert_eq!(small, generate(1, 0.1, 7).unwrap());
+        assert_ne!(small, generate(1, 0.1, 8).unwrap());
+        assert_eq!(small.lines().count(), 400);
+        assert_eq!(generate(1, 0.2, 7).unwrap().lines().count(), 800);
+
+        let grid = generate(4, 4.0, 7).unwrap();
+        assert_eq!(grid.lines().count(), 280);
+        assert_eq!(grid.lines().next().unwrap().len(), 280);
+    }
+}
@@ -1,9 +1,7 @@
-#![allow(dead_code)]
-
 use crate::position::Position;
 
 /// A 2D grid backed by a flat 1D array for better cache locality.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     data: Vec<T>,
     width: usize,
@@ -263,6 +261,26 @@ impl<T: Clone> std::ops::IndexMut<Position> for Grid<T> {
     }
 }
 
+/// Serialized as a list of rows
+impl<T: Clone + serde::Serialize> serde::Serialize for Grid<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter_rows().map(|row| row.collect::<Vec<_>>()))
+    }
+}
+
+impl<'de, T: Clone + serde::Deserialize<'de>> serde::Deserialize<'de> for Grid<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(serde::de::Error::custom(
+                "grid rows must all have the same width",
+            ));
+        }
+        Ok(Self::from_rows(rows))
+    }
+}
+
 /// Display for Grid<u8> that renders bytes as characters
 impl std::fmt::Display for Grid<u8> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -293,6 +311,26 @@ impl std::fmt::Display for Grid<char> {
     }
 }
 
+/// Display for Grid<bool> that renders lit cells as '#' and unlit cells as '.'
+impl std::fmt::Display for Grid<bool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let c = if self.data[self.index(x, y)] {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            if y < self.height - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Generic Debug for any Grid<T> where T: Debug
 impl<T: std::fmt::Debug> std::fmt::Debug for Grid<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -394,6 +432,21 @@ mod tests {
         assert_eq!(display, "abc\ndef\nghi");
     }
 
+    #[test]
+    fn test_display_bool() {
+        let grid = Grid::from_rows(vec![vec![true, false], vec![false, true]]);
+        assert_eq!(format!("{}", grid), "#.\n.#");
+    }
+
+    #[test]
+    fn test_serde() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, "[[1,2],[3,4]]");
+        assert_eq!(serde_json::from_str::<Grid<u8>>(&json).unwrap(), grid);
+        assert!(serde_json::from_str::<Grid<u8>>("[[1,2],[3]]").is_err());
+    }
+
     #[test]
     fn test_debug_u8() {
         let grid = Grid::parse("ab\ncd");
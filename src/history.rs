@@ -0,0 +1,160 @@
+//! What happened in previous runs, used to select days by past results, timings and
+//! changes since the last run.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::get_day_solvers;
+use crate::runner::{self, DayResult, Outcome};
+
+/// Default location of the run history, gitignored
+pub const HISTORY_FILE: &str = ".aoc-history.json";
+
+/// Stable 64-bit FNV-1a hash, unlike `DefaultHasher` it doesn't change between releases
+pub fn fingerprint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Fingerprints of a day's source and input, None if the day isn't implemented or has no
+/// readable input
+pub fn day_fingerprints(day: u8) -> Option<Fingerprints> {
+    let solvers = get_day_solvers(day)?;
+    let input = runner::read_input(day).ok()??;
+    Some(Fingerprints {
+        source: fingerprint(solvers.source.as_bytes()),
+        input: fingerprint(input.as_bytes()),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprints {
+    pub source: u64,
+    pub input: u64,
+}
+
+/// What is known about a day from previous runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DayRecord {
+    /// Result of the last verify run
+    pub passed: Option<bool>,
+    /// Time of the last fresh run of both parts, in ms
+    pub elapsed_ms: Option<f64>,
+    /// Source and input at the last run
+    pub fingerprints: Option<Fingerprints>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub days: BTreeMap<u8, DayRecord>,
+}
+
+impl History {
+    /// Load the history, a missing or unreadable file counts as no history
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, content + "\n")
+    }
+
+    pub fn get(&self, day: u8) -> Option<&DayRecord> {
+        self.days.get(&day)
+    }
+
+    /// Record a run. `passed` is the verify result, None if the run wasn't verified.
+    /// Timings are only kept for runs of both parts so they stay comparable.
+    pub fn record(
+        &mut self,
+        result: &DayResult,
+        part: Option<u8>,
+        passed: Option<bool>,
+        fingerprints: Option<Fingerprints>,
+    ) {
+        if matches!(result.outcome, Outcome::Skipped) {
+            return;
+        }
+        let record = self.days.entry(result.day).or_default();
+        if passed.is_some() {
+            record.passed = passed;
+        }
+        if part.is_none() && !result.cached && matches!(result.outcome, Outcome::Solved { .. }) {
+            record.elapsed_ms = Some(result.elapsed_ms());
+        }
+        if fingerprints.is_some() {
+            record.fingerprints = fingerprints;
+        }
+    }
+
+    /// Whether the source or input differ from the last run. Days never run count as
+    /// changed, days without an input (no current fingerprints) don't.
+    pub fn changed(&self, day: u8, current: Option<Fingerprints>) -> bool {
+        current.is_some() && self.get(day).and_then(|record| record.fingerprints) != current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solution;
+    use std::time::Duration;
+
+    fn solved(day: u8, ms: u64) -> DayResult {
+        DayResult {
+            day,
+            outcome: Outcome::Solved {
+                part1: Some(Solution::U8(1)),
+                part2: Some(Solution::U8(2)),
+            },
+            elapsed: Duration::from_millis(ms),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(fingerprint(b"L68\n"), fingerprint(b"L69\n"));
+    }
+
+    #[test]
+    fn test_record() {
+        let mut history = History::default();
+        let prints = Fingerprints {
+            source: 1,
+            input: 2,
+        };
+        history.record(&solved(3, 5), None, Some(false), Some(prints));
+        // a single part run keeps the timing and an unverified run keeps the result
+        history.record(&solved(3, 1), Some(1), None, Some(prints));
+
+        let record = history.get(3).unwrap();
+        assert_eq!(record.passed, Some(false));
+        assert_eq!(record.elapsed_ms, Some(5.0));
+
+        assert!(!history.changed(3, Some(prints)));
+        let edited = Fingerprints { input: 3, ..prints };
+        assert!(history.changed(3, Some(edited)));
+        assert!(history.changed(4, Some(prints)));
+        assert!(!history.changed(4, None));
+    }
+
+    #[test]
+    fn test_serde() {
+        let mut history = History::default();
+        history.record(&solved(1, 2), None, Some(true), None);
+        let json = serde_json::to_string(&history).unwrap();
+        assert_eq!(serde_json::from_str::<History>(&json).unwrap(), history);
+    }
+}
@@ -0,0 +1,566 @@
+//! Input inspection for starting a new puzzle: shape, characters and numbers of an input,
+//! and a guess at its format with the schema and parser helpers that fit it.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Grids with more distinct characters than this are more likely text
+const MAX_GRID_CHARS: usize = 12;
+
+/// A blank-line separated block of the input, with its own format guess
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// 1-based line of the first line
+    pub start: usize,
+    pub lines: usize,
+    pub format: Format,
+}
+
+/// Range of the integer tokens in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Numbers {
+    pub count: usize,
+    pub min: i128,
+    pub max: i128,
+    /// Some token didn't even fit an i128
+    pub huge: bool,
+}
+
+impl Numbers {
+    /// Warnings about types the values don't fit
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.huge || self.max > i128::from(u64::MAX) || self.min < i128::from(i64::MIN) {
+            warnings.push("values exceed 64 bits, use i128/u128 or bigint::BigInt".to_string());
+        } else if self.max > i128::from(u32::MAX) || self.min < i128::from(i32::MIN) {
+            warnings.push("values exceed i32 and u32, use i64/u64".to_string());
+        } else if self.max > i128::from(i32::MAX) {
+            warnings.push("values exceed i32, use u32 or a 64-bit type".to_string());
+        }
+        warnings
+    }
+}
+
+/// Guess at the format of a section or the whole input
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    /// Rectangular grid of characters, like day 4 and day 7
+    Grid { chars: String },
+    /// One number per line, like day 3, `signed` if some are negative
+    Numbers { signed: bool },
+    /// A letter followed by a number per line, like day 1
+    Prefixed { letters: String },
+    /// `a-b` ranges, one per line like day 5 or comma separated like day 2
+    Ranges { per_line: bool },
+    /// Comma-separated coordinates of the same dimension per line, like day 8
+    Coordinates { dims: usize },
+    /// Comma-separated numbers, varying in count
+    CsvNumbers,
+    /// Whitespace-separated columns of numbers, maybe with a trailing line of operators,
+    /// like day 6
+    Columns { trailer: Option<String> },
+    /// Blank-line separated sections of different formats
+    Sections(Vec<Format>),
+    /// Nothing recognized
+    Text,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Grid { chars } => write!(f, "grid of {:?}", chars),
+            Format::Numbers { signed: false } => write!(f, "numbers, one per line"),
+            Format::Numbers { signed: true } => write!(f, "signed numbers, one per line"),
+            Format::Prefixed { letters } => write!(f, "one of {:?} and a number per line", letters),
+            Format::Ranges { per_line: true } => write!(f, "ranges, one per line"),
+            Format::Ranges { per_line: false } => write!(f, "comma-separated ranges"),
+            Format::Coordinates { dims } => write!(f, "{}D coordinates, one per line", dims),
+            Format::CsvNumbers => write!(f, "comma-separated numbers"),
+            Format::Columns { trailer: None } => write!(f, "columns of numbers"),
+            Format::Columns { trailer: Some(ops) } => {
+                write!(f, "columns of numbers with a line of {:?}", ops)
+            }
+            Format::Sections(formats) => {
+                let formats: Vec<String> = formats.iter().map(|f| f.to_string()).collect();
+                write!(f, "sections: {}", formats.join(", then "))
+            }
+            Format::Text => write!(f, "unrecognized text"),
+        }
+    }
+}
+
+impl Format {
+    /// Schema matching the format, as source code
+    pub fn schema(&self) -> String {
+        let list =
+            |item: &str, sep: &str| format!("Token::List {{ item: &[{item}], sep: &[{sep}] }}");
+        let range = "Token::Uint, Token::Lit(\"-\"), Token::Uint";
+        match self {
+            Format::Grid { chars } => format!("Schema::Grid({:?})", chars),
+            Format::Numbers { signed: false } => "Schema::Lines(&[Token::Uint])".to_string(),
+            Format::Numbers { signed: true } => "Schema::Lines(&[Token::Int])".to_string(),
+            Format::Prefixed { letters } => {
+                format!("Schema::Lines(&[Token::OneOf({:?}), Token::Uint])", letters)
+            }
+            Format::Ranges { per_line: true } => format!("Schema::Lines(&[{}])", range),
+            Format::Ranges { per_line: false } => {
+                format!("Schema::Lines(&[{}])", list(range, "Token::Lit(\",\")"))
+            }
+            Format::Coordinates { dims } => {
+                let tokens = vec!["Token::Int"; *dims].join(", Token::Lit(\",\"), ");
+                format!("Schema::Lines(&[{}])", tokens)
+            }
+            Format::CsvNumbers => {
+                format!(
+                    "Schema::Lines(&[{}])",
+                    list("Token::Int", "Token::Lit(\",\")")
+                )
+            }
+            Format::Columns { trailer } => {
+                let body = format!(
+                    "&[Token::OptWs, {}, Token::OptWs]",
+                    list("Token::Uint", "Token::Ws")
+                );
+                match trailer {
+                    None => format!("Schema::Lines({})", body),
+                    Some(ops) => format!(
+                        "Schema::Trailer {{ body: {}, last: &[Token::OptWs, {}, Token::OptWs] }}",
+                        body,
+                        list(&format!("Token::OneOf({:?})", ops), "Token::Ws")
+                    ),
+                }
+            }
+            Format::Sections(formats) => {
+                let schemas: Vec<String> = formats.iter().map(Format::schema).collect();
+                format!("Schema::Sections(&[{}])", schemas.join(", "))
+            }
+            Format::Text => "Schema::Any".to_string(),
+        }
+    }
+
+    /// How to parse the format with the shared helpers
+    pub fn helper(&self) -> String {
+        match self {
+            Format::Grid { .. } => {
+                "Grid::parse(input), then Position/neighbors helpers (like day4, day7)".to_string()
+            }
+            Format::Numbers { .. } => {
+                "input.lines().map(error::parse::<T>) (like day3)".to_string()
+            }
+            Format::Prefixed { .. } => {
+                "split_at(1) per line, error::parse the number (like day1)".to_string()
+            }
+            Format::Ranges { per_line: true } => {
+                "split_once('-') per line, error::parse both ends (like day5)".to_string()
+            }
+            Format::Ranges { per_line: false } => {
+                "split(','), then split_once('-') and error::parse both ends (like day2)"
+                    .to_string()
+            }
+            Format::Coordinates { .. } => {
+                "split(',') per line, error::parse each field (like day8)".to_string()
+            }
+            Format::CsvNumbers => "split(',') per line, error::parse each field".to_string(),
+            Format::Columns { .. } => {
+                "split_whitespace() per line, error::parse each column (like day6)".to_string()
+            }
+            Format::Sections(formats) => {
+                let helpers: Vec<String> = formats.iter().map(Format::helper).collect();
+                format!("split_once(\"\\n\\n\"), then {}", helpers.join("; then "))
+            }
+            Format::Text => "no shared helper fits, parse by hand".to_string(),
+        }
+    }
+}
+
+/// Everything `inspect` reports about an input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inspection {
+    pub lines: usize,
+    pub blank_lines: usize,
+    /// Widths in characters of the non-blank lines
+    pub min_width: usize,
+    pub max_width: usize,
+    /// All non-blank lines have the same width
+    pub rectangular: bool,
+    /// Count of each character, line breaks excluded
+    pub histogram: BTreeMap<char, usize>,
+    pub sections: Vec<Section>,
+    pub numbers: Option<Numbers>,
+    pub format: Format,
+}
+
+impl Inspection {
+    /// Characters by count, most frequent first
+    pub fn ranked_chars(&self) -> Vec<(char, usize)> {
+        let mut chars: Vec<(char, usize)> = self.histogram.iter().map(|(&c, &n)| (c, n)).collect();
+        chars.sort_by_key(|&(c, n)| (std::cmp::Reverse(n), c));
+        chars
+    }
+
+    /// The report as printed by `inspect`, ending with the suggested schema and parser
+    pub fn render(&self, day: u8) -> String {
+        let mut text = format!("=== Day {:02} input ===\n", day);
+        text += &format!("  · Lines: {} ({} blank)\n", self.lines, self.blank_lines);
+        let shape = if self.rectangular {
+            "rectangular"
+        } else {
+            "not rectangular"
+        };
+        text += &format!(
+            "  · Width: {}-{}, {}\n",
+            self.min_width, self.max_width, shape
+        );
+
+        let chars: Vec<String> = self
+            .ranked_chars()
+            .iter()
+            .map(|(c, count)| format!("{:?} {}", c, count))
+            .collect();
+        text += &format!("  · Characters: {}\n", chars.join(", "));
+
+        if self.sections.len() > 1 {
+            text += &format!("  · Sections: {}\n", self.sections.len());
+            for section in &self.sections {
+                text += &format!(
+                    "      lines {}-{}: {}\n",
+                    section.start,
+                    section.start + section.lines - 1,
+                    section.format
+                );
+            }
+        }
+
+        match &self.numbers {
+            Some(numbers) => {
+                let huge = if numbers.huge {
+                    " (some beyond i128)"
+                } else {
+                    ""
+                };
+                text += &format!(
+                    "  · Numbers: {}, from {} to {}{}\n",
+                    numbers.count, numbers.min, numbers.max, huge
+                );
+                for warning in numbers.warnings() {
+                    text += &format!("      warning: {}\n", warning);
+                }
+            }
+            None => text += "  · Numbers: none\n",
+        }
+
+        text += &format!("  · Format: {}\n", self.format);
+        text += &format!("  · Schema: {}\n", self.format.schema());
+        text += &format!("  · Parse with: {}\n", self.format.helper());
+        text
+    }
+}
+
+pub fn inspect(input: &str) -> Inspection {
+    let lines: Vec<&str> = input.lines().collect();
+    let widths: Vec<usize> = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().count())
+        .collect();
+    let mut histogram = BTreeMap::new();
+    for c in input.chars().filter(|&c| c != '\n' && c != '\r') {
+        *histogram.entry(c).or_insert(0) += 1;
+    }
+
+    let sections = sections(&lines);
+    let format = match sections.as_slice() {
+        [] => Format::Text,
+        [section] => section.format.clone(),
+        sections => Format::Sections(sections.iter().map(|s| s.format.clone()).collect()),
+    };
+
+    Inspection {
+        lines: lines.len(),
+        blank_lines: lines.len() - widths.len(),
+        min_width: widths.iter().copied().min().unwrap_or(0),
+        max_width: widths.iter().copied().max().unwrap_or(0),
+        rectangular: widths.windows(2).all(|pair| pair[0] == pair[1]),
+        histogram,
+        sections,
+        numbers: numbers(input),
+        format,
+    }
+}
+
+fn sections(lines: &[&str]) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    for i in 0..=lines.len() {
+        if i == lines.len() || lines[i].trim().is_empty() {
+            if i > start {
+                sections.push(Section {
+                    start: start + 1,
+                    lines: i - start,
+                    format: guess(&lines[start..i]),
+                });
+            }
+            start = i + 1;
+        }
+    }
+    sections
+}
+
+/// Integer tokens: digit runs, with a `-` sign unless it follows a digit or letter
+/// (then it's a separator, like in ranges)
+fn numbers(input: &str) -> Option<Numbers> {
+    let bytes = input.as_bytes();
+    let mut stats: Option<Numbers> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let negative = start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+        let token = &input[start - usize::from(negative)..i];
+        let value = token.parse::<i128>().ok();
+
+        let entry = stats.get_or_insert(Numbers {
+            count: 0,
+            min: i128::MAX,
+            max: i128::MIN,
+            huge: false,
+        });
+        entry.count += 1;
+        match value {
+            Some(value) => {
+                entry.min = entry.min.min(value);
+                entry.max = entry.max.max(value);
+            }
+            None => entry.huge = true,
+        }
+    }
+    stats
+}
+
+fn is_uint(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_int(s: &str) -> bool {
+    is_uint(s.strip_prefix('-').unwrap_or(s))
+}
+
+fn is_range(s: &str) -> bool {
+    s.split_once('-')
+        .is_some_and(|(start, end)| is_uint(start) && is_uint(end))
+}
+
+/// Comma-separated fields of a line, trimmed
+fn fields(line: &str) -> Vec<&str> {
+    line.split(',').map(str::trim).collect()
+}
+
+/// Whitespace-separated numbers, at least two
+fn is_number_row(line: &str) -> bool {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    words.len() > 1 && words.iter().all(|word| is_uint(word))
+}
+
+/// Sorted distinct characters
+fn distinct(chars: impl Iterator<Item = char>) -> String {
+    let mut chars: Vec<char> = chars.collect();
+    chars.sort();
+    chars.dedup();
+    chars.into_iter().collect()
+}
+
+/// Guess the format of a block of non-blank lines, most specific first
+fn guess(lines: &[&str]) -> Format {
+    let all = |f: fn(&str) -> bool| lines.iter().all(|line| f(line));
+
+    if all(|line| is_int(line.trim())) {
+        let signed = lines.iter().any(|line| line.trim().starts_with('-'));
+        return Format::Numbers { signed };
+    }
+    if all(|line| is_range(line.trim())) {
+        return Format::Ranges { per_line: true };
+    }
+    if all(|line| {
+        fields(line.trim_end_matches(','))
+            .iter()
+            .all(|f| is_range(f))
+    }) {
+        return Format::Ranges { per_line: false };
+    }
+    if all(|line| fields(line).len() > 1 && fields(line).iter().all(|f| is_int(f))) {
+        let dims = fields(lines[0]).len();
+        return match dims <= 4 && lines.iter().all(|line| fields(line).len() == dims) {
+            true => Format::Coordinates { dims },
+            false => Format::CsvNumbers,
+        };
+    }
+    if all(|line| line.len() > 1 && line.as_bytes()[0].is_ascii_alphabetic() && is_uint(&line[1..]))
+    {
+        let letters = distinct(lines.iter().filter_map(|line| line.chars().next()));
+        return Format::Prefixed { letters };
+    }
+
+    if let Some((last, body)) = lines.split_last()
+        && !body.is_empty()
+        && body.iter().all(|line| is_number_row(line))
+    {
+        if is_number_row(last) {
+            return Format::Columns { trailer: None };
+        }
+        let ops: Vec<&str> = last.split_whitespace().collect();
+        if ops.len() > 1 && ops.iter().all(|op| op.chars().count() == 1) {
+            let ops = distinct(ops.iter().filter_map(|op| op.chars().next()));
+            return Format::Columns { trailer: Some(ops) };
+        }
+    }
+
+    let width = lines[0].chars().count();
+    let chars = distinct(lines.iter().flat_map(|line| line.chars()));
+    if lines.len() > 1
+        && width > 1
+        && lines.iter().all(|line| line.chars().count() == width)
+        && chars.chars().count() <= MAX_GRID_CHARS
+        && !chars.chars().any(char::is_whitespace)
+    {
+        return Format::Grid { chars };
+    }
+    Format::Text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape() {
+        let inspection = inspect("..@\n@.@\n\n12\n");
+        assert_eq!(inspection.lines, 4);
+        assert_eq!(inspection.blank_lines, 1);
+        assert_eq!((inspection.min_width, inspection.max_width), (2, 3));
+        assert!(!inspection.rectangular);
+        assert_eq!(inspection.ranked_chars()[0], ('.', 3));
+        assert_eq!(inspection.histogram[&'@'], 3);
+        let starts: Vec<usize> = inspection.sections.iter().map(|s| s.start).collect();
+        assert_eq!(starts, [1, 4]);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let numbers = inspect("3-5\n-7, 10\nx-2").numbers.unwrap();
+        // `-` after a digit or letter is a separator, not a sign
+        assert_eq!(numbers.count, 5);
+        assert_eq!((numbers.min, numbers.max), (-7, 10));
+        assert!(numbers.warnings().is_empty());
+
+        let numbers = inspect("5000000000").numbers.unwrap();
+        assert_eq!(
+            numbers.warnings(),
+            ["values exceed i32 and u32, use i64/u64"]
+        );
+        let numbers = inspect("3000000000").numbers.unwrap();
+        assert_eq!(
+            numbers.warnings(),
+            ["values exceed i32, use u32 or a 64-bit type"]
+        );
+        let numbers = inspect(&"9".repeat(50)).numbers.unwrap();
+        assert!(numbers.huge);
+        assert!(numbers.warnings()[0].contains("BigInt"));
+        assert!(inspect("abc").numbers.is_none());
+    }
+
+    #[test]
+    fn test_formats() {
+        let format = |input: &str| inspect(input).format;
+        assert_eq!(
+            format("L68\nR30\nL5"),
+            Format::Prefixed {
+                letters: "LR".into()
+            }
+        );
+        assert_eq!(
+            format("11-22,95-115,\n998-1012"),
+            Format::Ranges { per_line: false }
+        );
+        assert_eq!(
+            format("987654321111111\n811111111111119"),
+            Format::Numbers { signed: false }
+        );
+        assert_eq!(format("12\n-7"), Format::Numbers { signed: true });
+        assert_eq!(
+            format("..@@.\n@@@.@\n....."),
+            Format::Grid { chars: ".@".into() }
+        );
+        assert_eq!(
+            format("123 328  51\n 45 64  387\n*   +   *  "),
+            Format::Columns {
+                trailer: Some("*+".into())
+            }
+        );
+        assert_eq!(
+            format("162,817,812\n57,618,57"),
+            Format::Coordinates { dims: 3 }
+        );
+        assert_eq!(format("1,2,3,4,5\n6,7"), Format::CsvNumbers);
+        assert_eq!(format("hello world\nfoo"), Format::Text);
+        assert_eq!(
+            format("3-5\n10-14\n\n1\n5"),
+            Format::Sections(vec![
+                Format::Ranges { per_line: true },
+                Format::Numbers { signed: false }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let expected = "\
+=== Day 05 input ===
+  · Lines: 4 (1 blank)
+  · Width: 1-4, not rectangular
+  · Characters: '1' 2, '5' 2, '-' 1, '3' 1
+  · Sections: 2
+      lines 1-1: ranges, one per line
+      lines 3-4: numbers, one per line
+  · Numbers: 4, from 1 to 15
+  · Format: sections: ranges, one per line, then numbers, one per line
+  · Schema: Schema::Sections(&[Schema::Lines(&[Token::Uint, Token::Lit(\"-\"), Token::Uint]), Schema::Lines(&[Token::Uint])])
+  · Parse with: split_once(\"\\n\\n\"), then split_once('-') per line, error::parse both ends (like day5); then input.lines().map(error::parse::<T>) (like day3)
+";
+        assert_eq!(inspect("3-15\n\n1\n5\n").render(5), expected);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        assert_eq!(
+            Format::Coordinates { dims: 2 }.schema(),
+            "Schema::Lines(&[Token::Int, Token::Lit(\",\"), Token::Int])"
+        );
+        assert_eq!(
+            Format::Numbers { signed: true }.schema(),
+            "Schema::Lines(&[Token::Int])"
+        );
+        let sections = Format::Sections(vec![
+            Format::Ranges { per_line: true },
+            Format::Numbers { signed: false },
+        ]);
+        assert!(
+            sections
+                .schema()
+                .starts_with("Schema::Sections(&[Schema::Lines(")
+        );
+        assert!(sections.helper().contains("like day5"));
+        assert!(
+            Format::Grid { chars: ".#".into() }
+                .helper()
+                .starts_with("Grid::parse")
+        );
+    }
+}
@@ -0,0 +1,304 @@
+//! Per-part test cases of a verify run, summarized for the text output and written as
+//! JUnit XML for CI test reports.
+
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answers::Answers;
+use crate::runner::{DayResult, Outcome};
+
+/// How a part fared against the stored answers
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Passed,
+    /// The answer differs from the stored one
+    Failed {
+        expected: String,
+    },
+    /// The day failed or had invalid input, so the part has no answer
+    Error(String),
+    /// The day has no input
+    Skipped,
+    /// There is no stored answer to compare with
+    NoExpected,
+}
+
+/// One part of one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub actual: Option<String>,
+    /// Parts solved together share the day's time evenly
+    pub elapsed: Duration,
+}
+
+/// Test cases for the parts of a day run. `part` is the part selection of the run.
+pub fn cases(result: &DayResult, part: Option<u8>, expected: Option<&Answers>) -> Vec<TestCase> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let elapsed = result.elapsed / parts.len() as u32;
+    parts
+        .into_iter()
+        .map(|part| {
+            let actual = result.part(part);
+            let status = match (&result.outcome, actual, expected) {
+                (Outcome::Skipped, ..) => Status::Skipped,
+                (Outcome::InvalidInput(errors), ..) => {
+                    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                    Status::Error(format!("invalid input:\n{}", messages.join("\n")))
+                }
+                (Outcome::Failed(err), ..) => Status::Error(err.to_string()),
+                (Outcome::Solved { .. }, None, _) => Status::Error("part was not solved".into()),
+                (Outcome::Solved { .. }, Some(_), None) => Status::NoExpected,
+                (Outcome::Solved { .. }, Some(sol), Some(exp)) if exp.matches(part, sol) => {
+                    Status::Passed
+                }
+                (Outcome::Solved { .. }, Some(_), Some(exp)) => Status::Failed {
+                    expected: exp.part(part).to_string(),
+                },
+            };
+            TestCase {
+                day: result.day,
+                part,
+                status,
+                actual: actual.map(|sol| sol.to_string()),
+                elapsed,
+            }
+        })
+        .collect()
+}
+
+/// Counts of test cases by status. Wrong answers are failures, parts without an answer are
+/// errors, as JUnit tells them apart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub errors: usize,
+    pub skipped: usize,
+    pub no_expected: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, case: &TestCase) {
+        match case.status {
+            Status::Passed => self.passed += 1,
+            Status::Failed { .. } => self.failed += 1,
+            Status::Error(_) => self.errors += 1,
+            Status::Skipped => self.skipped += 1,
+            Status::NoExpected => self.no_expected += 1,
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a TestCase> for Summary {
+    fn from_iter<I: IntoIterator<Item = &'a TestCase>>(iter: I) -> Self {
+        let mut summary = Summary::default();
+        for case in iter {
+            summary.add(case);
+        }
+        summary
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} errors, {} skipped, {} without expected answer",
+            self.passed, self.failed, self.errors, self.skipped, self.no_expected
+        )
+    }
+}
+
+/// Escape text for XML attributes and content
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // not allowed in XML 1.0, ANSI colors in error messages would otherwise break it
+            c if c.is_control() && !matches!(c, '\n' | '\t' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render test cases as JUnit XML, one test suite per day
+pub fn to_xml(cases: &[TestCase]) -> String {
+    let seconds = |elapsed: Duration| format!("{:.6}", elapsed.as_secs_f64());
+    let total: Summary = cases.iter().collect();
+    let total_time: Duration = cases.iter().map(|case| case.elapsed).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml += &format!(
+        "<testsuites name=\"advent-2025\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+        cases.len(),
+        total.failed,
+        total.errors,
+        total.skipped,
+        seconds(total_time)
+    );
+    for day_cases in cases.chunk_by(|a, b| a.day == b.day) {
+        let summary: Summary = day_cases.iter().collect();
+        let time: Duration = day_cases.iter().map(|case| case.elapsed).sum();
+        xml += &format!(
+            "  <testsuite name=\"day{:02}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+            day_cases[0].day,
+            day_cases.len(),
+            summary.failed,
+            summary.errors,
+            summary.skipped,
+            seconds(time)
+        );
+        for case in day_cases {
+            xml += &format!(
+                "    <testcase classname=\"day{:02}\" name=\"part{}\" time=\"{}\">\n",
+                case.day,
+                case.part,
+                seconds(case.elapsed)
+            );
+            let actual = case.actual.as_deref().unwrap_or("");
+            match &case.status {
+                Status::Passed => {}
+                Status::Failed { expected } => {
+                    xml += &format!(
+                        "      <failure message=\"expected {}, got {}\" type=\"WrongAnswer\">expected: {}\nactual: {}</failure>\n",
+                        escape(expected),
+                        escape(actual),
+                        escape(expected),
+                        escape(actual)
+                    );
+                }
+                Status::Error(message) => {
+                    let first_line = message.lines().next().unwrap_or("");
+                    xml += &format!(
+                        "      <error message=\"{}\" type=\"SolveError\">{}</error>\n",
+                        escape(first_line),
+                        escape(message)
+                    );
+                }
+                Status::Skipped => xml += "      <skipped message=\"no input file\"/>\n",
+                Status::NoExpected => {}
+            }
+            if let Some(actual) = &case.actual {
+                let note = match case.status {
+                    Status::NoExpected => " (no expected answer)",
+                    _ => "",
+                };
+                xml += &format!(
+                    "      <system-out>answer: {}{}</system-out>\n",
+                    escape(actual),
+                    note
+                );
+            }
+            xml += "    </testcase>\n";
+        }
+        xml += "  </testsuite>\n";
+    }
+    xml += "</testsuites>\n";
+    xml
+}
+
+pub fn write(path: impl AsRef<Path>, cases: &[TestCase]) -> io::Result<()> {
+    fs::write(path, to_xml(cases))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    fn answers(part1: &str, part2: &str) -> Answers {
+        Answers {
+            part1: part1.into(),
+            part2: part2.into(),
+        }
+    }
+
+    #[test]
+    fn test_cases() {
+        let result = runner::solve(1, DAY1_INPUT, None);
+        let cases = cases(&result, None, Some(&answers("3", "7")));
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].status, Status::Passed);
+        assert_eq!(
+            cases[1].status,
+            Status::Failed {
+                expected: "7".into()
+            }
+        );
+        assert_eq!(cases[1].actual.as_deref(), Some("6"));
+        assert!(cases[0].elapsed * 2 <= result.elapsed);
+
+        let single = super::cases(&result, Some(2), None);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].status, Status::NoExpected);
+    }
+
+    #[test]
+    fn test_error_cases() {
+        let failed = runner::solve(1, "X1", None);
+        let cases = cases(&failed, None, Some(&answers("3", "6")));
+        assert!(cases.iter().all(|c| matches!(c.status, Status::Error(_))));
+
+        let skipped = DayResult {
+            day: 2,
+            outcome: Outcome::Skipped,
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        let summary: Summary = cases
+            .iter()
+            .chain(&super::cases(&skipped, Some(1), None))
+            .collect();
+        assert_eq!(
+            summary.to_string(),
+            "0 passed, 0 failed, 2 errors, 1 skipped, 0 without expected answer"
+        );
+    }
+
+    #[test]
+    fn test_xml() {
+        let result = runner::solve(1, DAY1_INPUT, None);
+        let mut all = cases(&result, None, Some(&answers("3", "<7>")));
+        all.extend(cases(&runner::solve(5, "x", None), Some(1), None));
+        let xml = to_xml(&all);
+
+        assert!(
+            xml.contains(
+                "<testsuites name=\"advent-2025\" tests=\"3\" failures=\"1\" errors=\"1\""
+            )
+        );
+        assert!(xml.contains("<testsuite name=\"day01\" tests=\"2\" failures=\"1\" errors=\"0\""));
+        assert!(xml.contains("<testsuite name=\"day05\" tests=\"1\" failures=\"0\" errors=\"1\""));
+        assert!(xml.contains("<testcase classname=\"day01\" name=\"part2\""));
+        assert!(xml.contains("message=\"expected &lt;7&gt;, got 6\""));
+        assert!(xml.contains("<testsuite name=\"day05\""));
+        assert!(xml.contains("<error message="));
+        assert_eq!(
+            xml.matches("<testcase ").count(),
+            xml.matches("</testcase>").count()
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a<b & \"c\""), "a&lt;b &amp; &quot;c&quot;");
+        assert_eq!(escape("\u{1b}[31mred\u{1b}[0m\n"), "[31mred[0m\n");
+    }
+}
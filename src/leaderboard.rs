@@ -0,0 +1,520 @@
+//! Private leaderboards, from the JSON the site serves at
+//! `https://adventofcode.com/{year}/leaderboard/private/view/{id}.json`: star times after
+//! each day's unlock, how the standings moved day by day, and local scores recomputed from
+//! the star times.
+//!
+//! A day's stars score like the site's local score: with N members, the first to get a star
+//! gets N points, the second N - 1, and so on.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::answers::Answers;
+use crate::fetch;
+use crate::history::fingerprint;
+
+/// Start of the names of the files fetched leaderboards are cached in, one per URL,
+/// gitignored
+pub const LEADERBOARD_CACHE: &str = ".aoc-leaderboard";
+
+/// The site asks for private leaderboards to be fetched at most every 15 minutes
+pub const MIN_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+/// Puzzles unlock at midnight US Eastern time, 05:00 UTC
+const UNLOCK_HOUR_UTC: i64 = 5;
+
+/// Cache file of the leaderboard at `url`, so that leaderboards don't overwrite each other
+pub fn cache_path(url: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}-{:016x}.json",
+        LEADERBOARD_CACHE,
+        fingerprint(url.as_bytes())
+    ))
+}
+
+/// The leaderboard JSON at `url`, from the cache while it is younger than [`MIN_REFRESH`].
+/// `refresh` insists on fetching, which is refused while the cache is that young.
+pub fn fetch(url: &str, refresh: bool) -> Result<String, String> {
+    let path = cache_path(url);
+    if refresh {
+        refresh_allowed(fetch::age(&path))?;
+    }
+    fetch::cached(&path, Some(MIN_REFRESH), refresh, || {
+        fetch::get(url, fetch::session().as_deref())
+    })
+}
+
+/// Whether a cached copy of age `age` (None: no copy) may be fetched again
+fn refresh_allowed(age: Option<Duration>) -> Result<(), String> {
+    match age {
+        Some(age) if age < MIN_REFRESH => Err(format!(
+            "the leaderboard was fetched {} ago and the site asks for at most one fetch every \
+             {} minutes, try again in {}",
+            format_duration(age),
+            MIN_REFRESH.as_secs() / 60,
+            format_duration(MIN_REFRESH - age)
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Leaderboard {
+    /// Year of the event, like `"2025"`
+    pub event: String,
+    /// Members by id, as strings
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// None for anonymous users
+    pub name: Option<String>,
+    #[serde(default)]
+    pub stars: u32,
+    /// Local score as computed by the site
+    #[serde(default)]
+    pub local_score: u64,
+    /// Stars by day and part, both as strings
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    /// Unix time the star was earned
+    pub get_star_ts: i64,
+    /// Order of the star on the site, breaks ties between equal times
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    /// Name shown for the member, the site's placeholder for anonymous users
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star(&self, day: u8, part: u8) -> Option<Star> {
+        let parts = self.completion_day_level.get(&day.to_string())?;
+        parts.get(&part.to_string()).copied()
+    }
+
+    /// Time between a day's first and second star
+    pub fn delta(&self, day: u8) -> Option<Duration> {
+        let (first, second) = (self.star(day, 1)?, self.star(day, 2)?);
+        Some(Duration::from_secs(
+            (second.get_star_ts - first.get_star_ts).max(0) as u64,
+        ))
+    }
+
+    /// Whether `name` is this member's name or id
+    pub fn is(&self, name: &str) -> bool {
+        self.name.as_deref() == Some(name) || self.id.to_string() == name
+    }
+}
+
+/// A member's position after a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Standing {
+    pub id: u64,
+    /// Points from the day's stars
+    pub points: u64,
+    /// Local score over all days so far
+    pub score: u64,
+    /// 1 for the highest score, members with equal scores share a rank
+    pub rank: usize,
+    /// Places gained since the previous day, negative if lost
+    pub change: i64,
+}
+
+impl Leaderboard {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|err| format!("not a private leaderboard: {}", err))
+    }
+
+    /// Days on which anyone has a star
+    pub fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self
+            .members
+            .values()
+            .flat_map(|member| member.completion_day_level.keys())
+            .filter_map(|day| day.parse().ok())
+            .collect();
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    /// Members in the order of their ids
+    pub fn members(&self) -> impl Iterator<Item = &Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|member| member.id);
+        members.into_iter()
+    }
+
+    pub fn member(&self, name: &str) -> Option<&Member> {
+        self.members().find(|member| member.is(name))
+    }
+
+    /// Points each member got for one part of a day
+    fn points(&self, day: u8, part: u8) -> BTreeMap<u64, u64> {
+        let mut finishers: Vec<(Star, u64)> = self
+            .members()
+            .filter_map(|member| Some((member.star(day, part)?, member.id)))
+            .collect();
+        finishers.sort_by_key(|&(star, id)| (star.get_star_ts, star.star_index, id));
+        let count = self.members.len() as u64;
+        finishers
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, id))| (id, count - i as u64))
+            .collect()
+    }
+
+    /// Standings after each day with stars, in the order of [`Leaderboard::days`]
+    pub fn standings(&self) -> Vec<(u8, Vec<Standing>)> {
+        let mut scores: BTreeMap<u64, u64> = self.members().map(|m| (m.id, 0)).collect();
+        let mut ranks: BTreeMap<u64, usize> = BTreeMap::new();
+        let mut standings = Vec::new();
+        for day in self.days() {
+            let mut points: BTreeMap<u64, u64> = BTreeMap::new();
+            for part in [1, 2] {
+                for (id, earned) in self.points(day, part) {
+                    *points.entry(id).or_default() += earned;
+                }
+            }
+            for (id, earned) in &points {
+                *scores.entry(*id).or_default() += earned;
+            }
+
+            let mut day_standings: Vec<Standing> = scores
+                .iter()
+                .map(|(&id, &score)| {
+                    // competition ranking: one more than the number of higher scores
+                    let rank = 1 + scores.values().filter(|&&other| other > score).count();
+                    Standing {
+                        id,
+                        points: points.get(&id).copied().unwrap_or(0),
+                        score,
+                        rank,
+                        change: ranks.get(&id).map_or(0, |&old| old as i64 - rank as i64),
+                    }
+                })
+                .collect();
+            day_standings.sort_by_key(|standing| (standing.rank, standing.id));
+            ranks = day_standings.iter().map(|s| (s.id, s.rank)).collect();
+            standings.push((day, day_standings));
+        }
+        standings
+    }
+
+    /// Local score of each member recomputed from the star times
+    pub fn local_scores(&self) -> BTreeMap<u64, u64> {
+        match self.standings().last() {
+            Some((_, standings)) => standings.iter().map(|s| (s.id, s.score)).collect(),
+            None => self.members().map(|member| (member.id, 0)).collect(),
+        }
+    }
+
+    /// Unix time at which `day` unlocked, None if the event isn't a year
+    pub fn unlock(&self, day: u8) -> Option<i64> {
+        let year: i64 = self.event.parse().ok()?;
+        Some(days_from_civil(year, 12, i64::from(day)) * 86_400 + UNLOCK_HOUR_UTC * 3600)
+    }
+
+    /// Time from the unlock of `day` to `star`
+    pub fn after_unlock(&self, day: u8, star: Star) -> Option<Duration> {
+        let seconds = star.get_star_ts - self.unlock(day)?;
+        Some(Duration::from_secs(seconds.max(0) as u64))
+    }
+
+    /// The overall standings, then each day's stars and how the ranks moved
+    pub fn render(&self) -> String {
+        let scores = self.local_scores();
+        let standings = self.standings();
+        let name_width = self
+            .members()
+            .map(|member| member.display_name().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let mut text = format!(
+            "=== Leaderboard {} ({} members) ===\n\n",
+            self.event,
+            self.members.len()
+        );
+        text += &format!("Rank  {:<name_width$}  Stars  Score  Recomputed\n", "Name");
+        let overall = standings
+            .last()
+            .map(|(_, day)| day.clone())
+            .unwrap_or_default();
+        let rank = |member: &Member| overall.iter().find(|s| s.id == member.id).map(|s| s.rank);
+        let mut members: Vec<&Member> = self.members().collect();
+        members.sort_by_key(|member| (rank(member).unwrap_or(usize::MAX), member.id));
+        for member in &members {
+            let recomputed = scores.get(&member.id).copied().unwrap_or(0);
+            text += &format!(
+                "{:>4}  {:<name_width$}  {:>5}  {:>5}  {:>10}{}\n",
+                rank(member).unwrap_or(0),
+                member.display_name(),
+                member.stars,
+                member.local_score,
+                recomputed,
+                if recomputed == member.local_score {
+                    ""
+                } else {
+                    "  (differs)"
+                }
+            );
+        }
+
+        for (day, day_standings) in &standings {
+            text += &format!("\n=== Day {:02} ===\n", day);
+            text += &format!(
+                "  {:<name_width$}  {:>10}  {:>10}  {:>10}  Points  Rank\n",
+                "Name", "Star 1", "Star 2", "Delta"
+            );
+            for standing in day_standings {
+                let Some(member) = self.members().find(|m| m.id == standing.id) else {
+                    continue;
+                };
+                let star = |part| {
+                    let star = member.star(*day, part)?;
+                    self.after_unlock(*day, star).map(format_duration)
+                };
+                let (first, second) = (star(1), star(2));
+                if first.is_none() && second.is_none() {
+                    continue;
+                }
+                let change = match standing.change {
+                    0 => "=".to_string(),
+                    change => format!("{:+}", change),
+                };
+                text += &format!(
+                    "  {:<name_width$}  {:>10}  {:>10}  {:>10}  {:>6}  {:>4} {}\n",
+                    member.display_name(),
+                    first.unwrap_or_else(|| "-".into()),
+                    second.unwrap_or_else(|| "-".into()),
+                    member.delta(*day).map_or("-".into(), format_duration),
+                    standing.points,
+                    standing.rank,
+                    change
+                );
+            }
+        }
+        text
+    }
+
+    /// `member`'s stars next to the answers stored for each day, pointing out stars without
+    /// a stored answer and stored answers without a star
+    pub fn render_own_stars(&self, member: &Member, answers: &HashMap<u8, Answers>) -> String {
+        let mut text = format!("\n=== Own stars ({}) ===\n", member.display_name());
+        let mut days = self.days();
+        days.extend(answers.keys());
+        days.sort();
+        days.dedup();
+        for day in days {
+            let stored = answers.get(&day);
+            let notes: Vec<String> = [1, 2]
+                .into_iter()
+                .filter_map(|part| {
+                    let starred = member.star(day, part).is_some();
+                    let has_answer = stored.is_some_and(|answers| !answers.part(part).is_empty());
+                    match (starred, has_answer) {
+                        (true, false) => Some(format!("part {} answer not stored", part)),
+                        (false, true) => Some(format!("part {} stored but no star", part)),
+                        _ => None,
+                    }
+                })
+                .collect();
+            let stars: String = [1, 2]
+                .into_iter()
+                .map(|part| {
+                    if member.star(day, part).is_some() {
+                        '*'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect();
+            if notes.is_empty() {
+                text += &format!("  · Day {:02} {}\n", day, stars);
+            } else {
+                text += &format!("  · Day {:02} {}  {}\n", day, stars, notes.join(", "));
+            }
+        }
+        text
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Duration as `h:mm:ss`, hours unbounded
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Day 1 of 2025 unlocked at 1764565200
+    const LEADERBOARD: &str = r#"{
+        "event": "2025",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "alice", "stars": 4, "local_score": 11,
+                  "completion_day_level": {
+                      "1": {"1": {"get_star_ts": 1764565500, "star_index": 10},
+                            "2": {"get_star_ts": 1764566100, "star_index": 12}},
+                      "2": {"1": {"get_star_ts": 1764652000, "star_index": 30},
+                            "2": {"get_star_ts": 1764653000, "star_index": 33}}}},
+            "2": {"id": 2, "name": null, "stars": 1, "local_score": 3,
+                  "completion_day_level": {
+                      "1": {"1": {"get_star_ts": 1764565400, "star_index": 9}}}},
+            "3": {"id": 3, "name": "carol", "stars": 3, "local_score": 10,
+                  "completion_day_level": {
+                      "2": {"1": {"get_star_ts": 1764651700, "star_index": 29},
+                            "2": {"get_star_ts": 1764651900, "star_index": 31}},
+                      "1": {"1": {"get_star_ts": 1764570000, "star_index": 20}}}}
+        }
+    }"#;
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.days(), [1, 2]);
+        let alice = leaderboard.member("alice").unwrap();
+        assert_eq!(alice.delta(1), Some(Duration::from_secs(600)));
+        assert_eq!(leaderboard.member("3").unwrap().delta(1), None);
+        assert_eq!(
+            leaderboard.member("2").unwrap().display_name(),
+            "(anonymous user #2)"
+        );
+        assert!(Leaderboard::parse("[]").is_err());
+    }
+
+    #[test]
+    fn test_standings() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let standings = leaderboard.standings();
+        let ranks = |day: usize| -> Vec<(u64, u64, usize, i64)> {
+            standings[day]
+                .1
+                .iter()
+                .map(|s| (s.id, s.score, s.rank, s.change))
+                .collect()
+        };
+        // day 1: anonymous 3 + 0, alice 2 + 3, carol 1
+        assert_eq!(ranks(0), [(1, 5, 1, 0), (2, 3, 2, 0), (3, 1, 3, 0)]);
+        // day 2: carol first to both stars, 6 points to alice's 4
+        assert_eq!(ranks(1), [(1, 9, 1, 0), (3, 7, 2, 1), (2, 3, 3, -1)]);
+        assert_eq!(standings[1].1[1].points, 6);
+    }
+
+    #[test]
+    fn test_local_scores() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let scores = leaderboard.local_scores();
+        assert_eq!(scores, [(1, 9), (2, 3), (3, 7)].into());
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let expected = "\
+=== Leaderboard 2025 (3 members) ===
+
+Rank  Name                 Stars  Score  Recomputed
+   1  alice                    4     11           9  (differs)
+   2  carol                    3     10           7  (differs)
+   3  (anonymous user #2)      1      3           3
+
+=== Day 01 ===
+  Name                     Star 1      Star 2       Delta  Points  Rank
+  alice                   0:05:00     0:15:00     0:10:00       5     1 =
+  (anonymous user #2)     0:03:20           -           -       3     2 =
+  carol                   1:20:00           -           -       1     3 =
+
+=== Day 02 ===
+  Name                     Star 1      Star 2       Delta  Points  Rank
+  alice                   0:06:40     0:23:20     0:16:40       4     1 =
+  carol                   0:01:40     0:05:00     0:03:20       6     2 +1
+";
+        assert_eq!(leaderboard.render(), expected);
+    }
+
+    #[test]
+    fn test_render_own_stars() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let carol = leaderboard.member("carol").unwrap();
+        let answers = HashMap::from([
+            (
+                1,
+                Answers {
+                    part1: "3".into(),
+                    part2: "6".into(),
+                },
+            ),
+            (
+                2,
+                Answers {
+                    part1: "1".into(),
+                    part2: String::new(),
+                },
+            ),
+        ]);
+        let expected = "\n\
+=== Own stars (carol) ===
+  · Day 01 *   part 2 stored but no star
+  · Day 02 **  part 2 answer not stored
+";
+        assert_eq!(leaderboard.render_own_stars(carol, &answers), expected);
+    }
+
+    #[test]
+    fn test_refresh() {
+        let url = "https://adventofcode.com/2025/leaderboard/private/view/1.json";
+        assert_eq!(cache_path(url), cache_path(url));
+        assert_ne!(cache_path(url), cache_path(&url.replace('1', "2")));
+
+        assert!(refresh_allowed(None).is_ok());
+        assert!(refresh_allowed(Some(MIN_REFRESH)).is_ok());
+        let err = refresh_allowed(Some(Duration::from_secs(60))).unwrap_err();
+        assert!(err.contains("try again in 0:14:00"), "{err}");
+    }
+
+    #[test]
+    fn test_unlock() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(leaderboard.unlock(1), Some(1_764_565_200));
+        let star = leaderboard.member("alice").unwrap().star(1, 2).unwrap();
+        let after = leaderboard.after_unlock(1, star).unwrap();
+        assert_eq!(format_duration(after), "0:15:00");
+        assert_eq!(format_duration(Duration::from_secs(90_061)), "25:01:01");
+    }
+}
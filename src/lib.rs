@@ -0,0 +1,228 @@
+#![feature(linked_list_cursors)]
+#![feature(macro_metavar_expr)]
+#![feature(cfg_overflow_checks)]
+#![feature(alloc_error_hook)]
+//! Advent of Code 2025 solutions, plus the shared helpers and runner they are built on.
+//!
+//! The `advent-2025` binary is a thin CLI over [`runner::run_day`]; other tools and
+//! integration tests can use the registry and helpers directly.
+
+pub mod anonymize;
+pub mod answers;
+pub mod bigint;
+pub mod budget;
+pub mod cache;
+pub mod compare;
+pub mod config;
+pub mod crypt;
+pub mod determinism;
+pub mod error;
+pub mod fetch;
+pub mod ffi;
+pub mod generate;
+pub mod grid;
+pub mod history;
+pub mod inspect;
+pub mod junit;
+pub mod leaderboard;
+pub mod ocr;
+pub mod oracle;
+pub mod plugin;
+pub mod position;
+pub mod puzzle;
+pub mod runner;
+#[cfg(unix)]
+pub mod sandbox;
+pub mod scale;
+pub mod schema;
+pub mod select;
+pub mod server;
+pub mod shrink;
+pub mod solution;
+pub mod utils;
+pub mod visualize;
+
+/// Shared modules that solvers build on, by file name. Cached results depend on their
+/// source as well as the day's own, so every module a day can use must be listed here.
+pub const SHARED_SOURCES: &[(&str, &str)] = &[
+    ("bigint.rs", include_str!("bigint.rs")),
+    ("error.rs", include_str!("error.rs")),
+    ("grid.rs", include_str!("grid.rs")),
+    ("ocr.rs", include_str!("ocr.rs")),
+    ("position.rs", include_str!("position.rs")),
+    ("schema.rs", include_str!("schema.rs")),
+    ("solution.rs", include_str!("solution.rs")),
+    ("utils.rs", include_str!("utils.rs")),
+    ("visualize.rs", include_str!("visualize.rs")),
+];
+
+use std::fmt::{Debug, Formatter};
+use std::sync::OnceLock;
+
+use error::SolveError;
+use schema::Schema;
+use solution::{Day, SolutionPair, SolveResult};
+
+/// Solvers for each part. Built-in days point at their `Day` functions, plugins at wrappers
+/// around the plugin's C functions.
+pub type PartSolver = &'static (dyn Fn(&str) -> SolveResult + Send + Sync);
+pub type BothSolver = &'static (dyn Fn(&str) -> Result<SolutionPair, SolveError> + Send + Sync);
+
+/// A registered day with its input schema and solvers
+#[derive(Clone, Copy)]
+pub struct DaySolvers {
+    pub day: u8,
+    /// Source code of the day's module, for detecting changes. For plugins, the library
+    /// path and a fingerprint of its contents.
+    pub source: &'static str,
+    pub schema: &'static Schema,
+    pub part1: PartSolver,
+    pub part2: PartSolver,
+    pub both: BothSolver,
+}
+
+impl Debug for DaySolvers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DaySolvers")
+            .field("day", &self.day)
+            .field("schema", &self.schema)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Macro to register all day solutions.
+/// Generates module declarations and the registry.
+///
+/// Usage: `register_days!(day1, day2, day3, ...);`
+macro_rules! register_days {
+    ($($day:ident),* $(,)?) => {
+        // Generate module declarations
+        $(pub mod $day;)*
+
+        /// All implemented days, in order
+        const SOLVERS: &[DaySolvers] = &[
+            $(DaySolvers {
+                day: ${index()} as u8 + 1,
+                source: include_str!(concat!(stringify!($day), ".rs")),
+                schema: &<$day::Solution as Day>::SCHEMA,
+                part1: &$day::Solution::part1,
+                part2: &$day::Solution::part2,
+                both: &$day::Solution::solve,
+            },)*
+        ];
+    };
+}
+
+// Register all implemented days - just list them in order!
+register_days!(day1, day2, day3, day4, day5, day6, day7, day8);
+
+/// Built-in days plus any installed plugins, fixed on first use
+static REGISTRY: OnceLock<Vec<DaySolvers>> = OnceLock::new();
+
+/// All implemented days, in order: the built-in ones and loaded plugins
+pub fn days() -> &'static [DaySolvers] {
+    REGISTRY.get_or_init(|| SOLVERS.to_vec())
+}
+
+/// Solvers for a day, if it is implemented
+pub fn get_day_solvers(day: u8) -> Option<&'static DaySolvers> {
+    days().iter().find(|solvers| solvers.day == day)
+}
+
+/// Count of implemented days
+pub fn num_days() -> u8 {
+    days().len() as u8
+}
+
+/// Add plugin days to the registry. Only possible before the registry is first used,
+/// returns the days back otherwise.
+fn install_days(extra: Vec<DaySolvers>) -> Result<(), Vec<DaySolvers>> {
+    let mut all = SOLVERS.to_vec();
+    all.extend(&extra);
+    all.sort_by_key(|solvers| solvers.day);
+    REGISTRY.set(all).map_err(|_| extra)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        assert_eq!(num_days() as usize, days().len());
+        for (i, solvers) in SOLVERS.iter().enumerate() {
+            assert_eq!(solvers.day as usize, i + 1);
+        }
+        assert_eq!(get_day_solvers(1).map(|s| s.day), Some(1));
+        assert!(get_day_solvers(0).is_none());
+        assert!(get_day_solvers(num_days() + 1).is_none());
+    }
+
+    /// Modules `source` uses through `crate::`, including those of a `use crate::{..}` group
+    fn crate_paths(source: &str) -> Vec<&str> {
+        let ident = |text: &str| -> usize {
+            text.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(text.len())
+        };
+        let mut modules = Vec::new();
+        for (start, _) in source.match_indices("crate::") {
+            let rest = &source[start + "crate::".len()..];
+            let Some(group) = rest.strip_prefix('{') else {
+                modules.push(&rest[..ident(rest)]);
+                continue;
+            };
+            // the first name of each item at the group's top level
+            let mut depth = 0;
+            let mut item_start = true;
+            for (i, c) in group.char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' if depth == 0 => break,
+                    '}' => depth -= 1,
+                    ',' if depth == 0 => item_start = true,
+                    c if item_start && !c.is_whitespace() => {
+                        modules.push(&group[i..i + ident(&group[i..])]);
+                        item_start = false;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        modules
+    }
+
+    #[test]
+    fn test_shared_sources() {
+        // every module a day or a shared module uses must itself be shared
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut sources: Vec<(String, String)> = SHARED_SOURCES
+            .iter()
+            .map(|&(name, source)| (name.to_string(), source.to_string()))
+            .collect();
+        for entry in std::fs::read_dir(&src).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            if name.starts_with("day") {
+                sources.push((name, std::fs::read_to_string(&path).unwrap()));
+            }
+        }
+        assert!(sources.iter().any(|(name, _)| name == "day7.rs"));
+
+        for (name, source) in &sources {
+            for module in crate_paths(source) {
+                let file = format!("{}.rs", module);
+                if !src.join(&file).exists() {
+                    continue; // an item of lib.rs, like a macro or constant
+                }
+                assert!(
+                    SHARED_SOURCES.iter().any(|&(shared, _)| shared == file),
+                    "{name} uses {file}, which isn't in SHARED_SOURCES"
+                );
+            }
+        }
+        assert_eq!(
+            crate_paths("use crate::{error::E, grid::{self, G}};\nuse crate::ocr;"),
+            ["error", "grid", "ocr"]
+        );
+    }
+}
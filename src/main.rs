@@ -1,75 +1,84 @@
-#![feature(linked_list_cursors)]
-#![feature(macro_metavar_expr)]
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::collections::HashMap;
 use std::fs;
-use std::time::Instant;
-
-mod grid;
-mod position;
-mod solution;
-mod utils;
-
-use solution::{Day, Solution};
-
-const ANSWERS_FILE: &str = "answers.txt";
-
-/// Macro to register all day solutions.
-/// Generates module declarations and the dispatch function.
-///
-/// Usage: `register_days!(day1, day2, day3, ...);`
-macro_rules! register_days {
-    ($($day:ident),* $(,)?) => {
-        // Generate module declarations
-        $(mod $day;)*
-
-        // Solver function pointers for each part
-        type PartSolver = fn(&str) -> Solution;
-        type BothSolver = fn(&str) -> solution::SolutionPair;
-
-        struct DaySolvers {
-            part1: PartSolver,
-            part2: PartSolver,
-            both: BothSolver,
-        }
-
-        // Generate the dispatch function
-        fn get_day_solvers(day: u8) -> DaySolvers {
-            const SOLVERS: &[DaySolvers] = &[
-                $(DaySolvers {
-                    part1: $day::Solution::part1,
-                    part2: $day::Solution::part2,
-                    both: $day::Solution::solve,
-                },)*
-            ];
-
-            let idx = (day - 1) as usize;
-            if idx < SOLVERS.len() {
-                DaySolvers {
-                    part1: SOLVERS[idx].part1,
-                    part2: SOLVERS[idx].part2,
-                    both: SOLVERS[idx].both,
-                }
-            } else {
-                panic!("Day {} not implemented", day)
-            }
-        }
-
-        // Count of implemented days
-        fn num_days() -> u8 {
-            [$($day::Solution::solve,)*].len() as u8
-        }
-    };
-}
-
-// Register all implemented days - just list them in order!
-register_days!(day1, day2, day3, day4, day5, day6, day7, day8);
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use advent_2025::anonymize;
+use advent_2025::answers::{ANSWERS_FILE, Answers, load_answers, save_answers};
+use advent_2025::budget;
+use advent_2025::cache::{self, CACHE_FILE, Cache, CacheMode};
+use advent_2025::compare::{self, Export};
+use advent_2025::config::{CONFIG_FILE, Config};
+use advent_2025::crypt::{self, Key};
+use advent_2025::determinism;
+use advent_2025::error::SolveError;
+use advent_2025::fetch;
+use advent_2025::generate;
+use advent_2025::history::{self, HISTORY_FILE, History};
+use advent_2025::inspect;
+use advent_2025::junit::{self, Summary};
+use advent_2025::leaderboard::{self, Leaderboard};
+use advent_2025::oracle;
+use advent_2025::puzzle;
+use advent_2025::runner::{self, DayResult, Outcome};
+#[cfg(unix)]
+use advent_2025::sandbox;
+use advent_2025::scale;
+use advent_2025::select::{self, DaySet, Filters};
+use advent_2025::server::{Server, ServerConfig};
+use advent_2025::shrink::Predicate;
+use advent_2025::solution::Solution;
+use advent_2025::visualize::{self, Frame, Playback};
+use advent_2025::{days, num_days, plugin};
+
+/// How many schema violations to print before summarizing the rest
+const MAX_VIOLATIONS_SHOWN: usize = 5;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Day to run (omit to run all implemented days)
-    day: Option<u8>,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Days to run, like `3` or `1-5,8` (omit to run all implemented days)
+    days: Option<DaySet>,
+
+    /// Days to leave out, same syntax as the days to run
+    #[arg(short = 'x', long, value_name = "DAYS")]
+    exclude: Option<DaySet>,
+
+    /// Only run days whose last verify run failed
+    #[arg(long)]
+    only_failing: bool,
+
+    /// Only run days whose source or input changed since the last run
+    #[arg(long)]
+    changed: bool,
+
+    /// Only run the N slowest days, by timings recorded in earlier runs
+    #[arg(long, value_name = "N")]
+    slowest: Option<usize>,
+
+    /// Benchmark the days against a total time budget in ms, split evenly or by the
+    /// weights in aoc.toml
+    #[arg(long, value_name = "MS", value_parser = positive,
+          conflicts_with_all = ["save", "verify", "visualize"])]
+    budget: Option<f64>,
+
+    /// Solve each part N times with different hasher seeds and rayon thread counts, and
+    /// report the parts whose answers vary
+    #[arg(long, value_name = "N", conflicts_with_all = ["save", "verify", "visualize", "budget", "sandbox"],
+          value_parser = clap::value_parser!(u64).range(2..))]
+    determinism: Option<u64>,
+
+    /// Compare each part's answer with an external program's output. TEMPLATE is run through
+    /// the shell with {day}, {part} and {input} (the input file's path) filled in and the
+    /// input on stdin. Without TEMPLATE the commands come from aoc.toml.
+    #[arg(long, value_name = "TEMPLATE", num_args = 0..=1,
+          conflicts_with_all = ["save", "verify", "visualize", "budget", "sandbox", "determinism"])]
+    oracle: Option<Option<String>>,
 
     /// Run only part 1 or 2
     #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
@@ -82,106 +91,370 @@ struct Args {
     /// Verify answers against answers.txt
     #[arg(short, long)]
     verify: bool,
-}
 
-/// Stored answers for a day
-#[derive(Debug, Clone)]
-struct Answers {
-    part1: String,
-    part2: String,
+    /// Write a JUnit XML report with a test case per day and part to PATH (implies --verify)
+    #[arg(long, value_name = "PATH")]
+    junit: Option<PathBuf>,
+
+    /// Write the results with a reference benchmark of this machine to PATH as JSON, for
+    /// `compare`
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["part", "visualize"])]
+    export: Option<PathBuf>,
+
+    /// Name of the run in the export (default: the export file's name)
+    #[arg(long, value_name = "NAME", requires = "export")]
+    export_name: Option<String>,
+
+    /// Report days whose input, code and build profile are unchanged from the result cache
+    #[arg(long)]
+    cached: bool,
+
+    /// Don't read or update the result cache
+    #[arg(long, conflicts_with = "cached")]
+    no_cache: bool,
+
+    /// Play the frames emitted by solvers that support visualization
+    #[arg(long)]
+    visualize: bool,
+
+    /// Animation speed in frames per second
+    #[arg(long, default_value_t = 10, requires = "visualize",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    fps: u32,
+
+    /// Step through frames one at a time instead of animating
+    #[arg(long, requires = "visualize")]
+    step: bool,
+
+    /// Write frames as numbered text files under DIR/dayNN instead of playing them
+    #[arg(long, value_name = "DIR", requires = "visualize")]
+    frames_dir: Option<PathBuf>,
+
+    /// Solve each day in a child process under memory and CPU-time limits, so a day that
+    /// exceeds them fails with RESOURCE LIMIT instead of taking the runner down. Children are
+    /// forked, so this is refused once the runner has started other threads.
+    #[arg(long, conflicts_with_all = ["visualize", "budget"])]
+    sandbox: bool,
+
+    /// Address-space limit of sandboxed days in MiB
+    #[arg(long, value_name = "MIB", default_value_t = 4096, requires = "sandbox",
+          value_parser = clap::value_parser!(u64).range(1..))]
+    memory_limit: u64,
+
+    /// CPU-time limit of sandboxed days in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 60, requires = "sandbox",
+          value_parser = clap::value_parser!(u64).range(1..))]
+    cpu_limit: u64,
+
+    /// Directory of day plugins (shared libraries) to load
+    #[arg(long, value_name = "DIR", default_value = plugin::PLUGINS_DIR, global = true)]
+    plugins: PathBuf,
 }
 
-/// Load answers from answers.txt
-fn load_answers() -> HashMap<u8, Answers> {
-    let mut answers = HashMap::new();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Encrypt inputs and answers.txt into .enc files that can be committed
+    Encrypt {
+        /// Days whose input to encrypt (omit for all days with an input)
+        days: Vec<u8>,
+    },
+    /// Decrypt .enc inputs and answers back into plaintext files
+    Decrypt {
+        /// Days whose input to decrypt (omit for all days with an encrypted input)
+        days: Vec<u8>,
+
+        /// Overwrite existing plaintext files
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Generate a new encryption key in the key file
+    Keygen,
+    /// Describe a day's input: shape, characters, numbers and a guess at its format
+    Inspect {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Shrink a day's input to a small one that still shows a bug, and save it as an example
+    #[command(group = clap::ArgGroup::new("bug").required(true))]
+    Shrink {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Input to shrink (default: the day's puzzle input)
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Only look at part 1 or 2
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Bug: the part solvers and the combined solver disagree
+        #[arg(long, group = "bug")]
+        split: bool,
+
+        /// Bug: our answer differs from the oracle's (TEMPLATE as for --oracle, default from
+        /// aoc.toml)
+        #[arg(long, value_name = "TEMPLATE", num_args = 0..=1, group = "bug")]
+        oracle: Option<Option<String>>,
+
+        /// Bug: a shell command succeeds, with {day}, {part} and {input} filled in and the
+        /// input on stdin
+        #[arg(long, value_name = "COMMAND", group = "bug")]
+        predicate: Option<String>,
+    },
+    /// Anonymize a day's input into a regression fixture under tests/fixtures, with its
+    /// answers
+    Anonymize {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Seed of the randomized values, the same seed gives the same fixture
+        #[arg(long, default_value_t = generate::DEFAULT_SEED)]
+        seed: u64,
+
+        /// Input to anonymize (default: the day's puzzle input)
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+    },
+    /// Compare results exported with --export by teammates or on other machines
+    Compare {
+        /// Export files, times are scaled to the machine of the first
+        #[arg(required = true, num_args = 2.., value_name = "EXPORT")]
+        files: Vec<PathBuf>,
+    },
+    /// Generate a synthetic input in a day's format
+    Generate {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Size relative to an official input: lines, ranges, points or grid cells
+        #[arg(long, default_value_t = 1.0, value_parser = positive)]
+        scale: f64,
+
+        /// Seed of the generator, the same seed gives the same input
+        #[arg(long, default_value_t = generate::DEFAULT_SEED)]
+        seed: u64,
+
+        /// Write the input to PATH instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Time a day on generated inputs of increasing size and estimate its complexity
+    Scale {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Largest scale measured, the others halve down from it
+        #[arg(long, default_value_t = 4.0, value_parser = positive)]
+        max_scale: f64,
+
+        /// Number of scales measured
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(2..))]
+        steps: u64,
+
+        /// Seed of the generator
+        #[arg(long, default_value_t = generate::DEFAULT_SEED)]
+        seed: u64,
+    },
+    /// Show a private leaderboard: star times, rank changes and recomputed local scores
+    Leaderboard {
+        /// Read the leaderboard JSON from PATH
+        #[arg(long, value_name = "PATH", conflicts_with = "url")]
+        file: Option<PathBuf>,
+
+        /// Fetch the leaderboard JSON from URL (default: from aoc.toml), with the session
+        /// cookie from AOC_SESSION
+        #[arg(long, value_name = "URL")]
+        url: Option<String>,
+
+        /// Our name or member id, to show our stars next to our stored answers (default:
+        /// from aoc.toml)
+        #[arg(long, value_name = "NAME")]
+        me: Option<String>,
+
+        /// Fetch instead of using the cached copy, refused while it is less than 15 minutes
+        /// old
+        #[arg(long, conflicts_with = "file")]
+        refresh: bool,
+    },
+    /// Read a day's puzzle description in the terminal and extract its examples
+    Read {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Read the page from PATH instead of fetching it
+        #[arg(long, value_name = "PATH", conflicts_with = "url")]
+        file: Option<PathBuf>,
+
+        /// Page URL, with {day} filled in and the session cookie from AOC_SESSION
+        #[arg(long, value_name = "URL", default_value = puzzle::DEFAULT_URL)]
+        url: String,
+
+        /// Fetch the page again, like after solving part 1 to see part 2
+        #[arg(long, conflicts_with = "file")]
+        refresh: bool,
+
+        /// Examples to save as input/dayN/example*.txt without asking, like `1,3` or `all`
+        #[arg(long, value_name = "LIST")]
+        extract: Option<String>,
+
+        /// Don't style the text, even on a terminal
+        #[arg(long)]
+        plain: bool,
+    },
+    /// Serve the solvers as a JSON API over local HTTP
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+
+        #[arg(long, default_value_t = 8025)]
+        port: u16,
+
+        /// Largest accepted input, in bytes
+        #[arg(long, default_value_t = 1024 * 1024)]
+        max_body: usize,
+
+        /// Time allowed for solving a request, in ms
+        #[arg(long, default_value_t = 10_000)]
+        timeout: u64,
+
+        /// Solves running at once, timed out ones included (default: one per CPU)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        max_solves: Option<u64>,
+
+        /// Origin allowed to call the API from a browser (CORS)
+        #[arg(long)]
+        allow_origin: Option<String>,
+    },
+}
 
-    let content = match fs::read_to_string(ANSWERS_FILE) {
-        Ok(c) => c,
-        Err(_) => return answers,
-    };
+fn main() {
+    let mut args = Args::parse();
+    args.verify |= args.junit.is_some();
 
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
+    // before anything looks at the registry
+    for err in plugin::load(&args.plugins) {
+        eprintln!("Warning: {}", err);
+    }
 
-        // Format: "day: part1, part2"
-        if let Some((day_str, rest)) = line.split_once(':') {
-            if let Ok(day) = day_str.trim().parse::<u8>() {
-                let parts: Vec<&str> = rest.split(',').map(|s| s.trim()).collect();
-                if parts.len() == 2 {
-                    answers.insert(
-                        day,
-                        Answers {
-                            part1: parts[0].to_string(),
-                            part2: parts[1].to_string(),
-                        },
-                    );
-                }
-            }
+    if let Some(command) = &args.command {
+        if let Err(err) = run_command(command) {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
         }
+        return;
     }
 
-    answers
-}
-
-/// Save answers to answers.txt
-fn save_answers(answers: &HashMap<u8, Answers>) {
-    let mut days: Vec<_> = answers.keys().collect();
-    days.sort();
+    let mut history = History::load(HISTORY_FILE);
 
-    let content: String = days
-        .iter()
-        .map(|day| {
-            let a = &answers[day];
-            format!("{}: {}, {}", day, a.part1, a.part2)
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    fs::write(ANSWERS_FILE, content + "\n").expect("Failed to write answers file");
-}
+    let requested: Vec<u8> = match &args.days {
+        Some(days) => days.iter().collect(),
+        None => days().iter().map(|solvers| solvers.day).collect(),
+    };
+    let filters = Filters {
+        exclude: args.exclude.clone(),
+        only_failing: args.only_failing,
+        changed: args.changed,
+        slowest: args.slowest,
+    };
+    let days = select::select(requested, &filters, &history, history::day_fingerprints);
+    if days.is_empty() {
+        println!("No days selected");
+        return;
+    }
 
-fn main() {
-    let args = Args::parse();
+    if let Some(budget) = args.budget {
+        let within = run_budget(
+            &days,
+            Duration::try_from_secs_f64(budget / 1000.0).unwrap_or(Duration::MAX),
+        );
+        std::process::exit(if within { 0 } else { 1 });
+    }
+    if let Some(template) = &args.oracle {
+        let agreed = run_oracle(&days, args.part, template.as_deref());
+        std::process::exit(if agreed { 0 } else { 1 });
+    }
+    if let Some(runs) = args.determinism {
+        let stable = run_determinism(&days, args.part, runs as usize);
+        std::process::exit(if stable { 0 } else { 1 });
+    }
 
-    let days: Vec<u8> = match args.day {
-        Some(day) => vec![day],
-        None => (1..=num_days()).collect(),
+    // only needed, and only worth failing over, when verifying or saving
+    let mut stored_answers = if args.verify || args.save {
+        load_answers(ANSWERS_FILE).unwrap_or_else(|err| {
+            eprintln!("Error: cannot read {}: {}", ANSWERS_FILE, err);
+            std::process::exit(1);
+        })
+    } else {
+        HashMap::new()
     };
-
-    let mut stored_answers = load_answers();
     let mut all_passed = true;
+    let mut test_cases = Vec::new();
 
+    // cached days emit no frames, so visualizing always solves
+    let cache_mode = match (args.no_cache, args.cached && !args.visualize) {
+        (true, _) => CacheMode::Off,
+        (false, true) => CacheMode::Use,
+        (false, false) => CacheMode::Refresh,
+    };
+    let mut cache = match cache_mode {
+        CacheMode::Off => Cache::default(),
+        _ => Cache::load(CACHE_FILE),
+    };
+
+    let mut export = args.export.as_ref().map(|path| {
+        let name = args.export_name.clone().unwrap_or_else(|| {
+            let stem = path.file_stem().unwrap_or_default();
+            stem.to_string_lossy().into_owned()
+        });
+        Export::new(
+            &name,
+            cache::build_profile(),
+            compare::reference_benchmark(),
+        )
+    });
+
+    let solve = solver(&args);
     let total_start = Instant::now();
 
     for day in &days {
-        let result = run_day(*day, args.part, args.verify, stored_answers.get(day));
-
-        if args.save {
-            // Get existing answers or create new
-            let mut ans = stored_answers.get(day).cloned().unwrap_or(Answers {
-                part1: String::new(),
-                part2: String::new(),
-            });
-
-            // Update based on what we ran
-            if let Some(p1) = &result.part1 {
-                ans.part1 = p1.to_string();
-            }
-            if let Some(p2) = &result.part2 {
-                ans.part2 = p2.to_string();
-            }
-
-            if result.part1.is_some() || result.part2.is_some() {
-                stored_answers.insert(*day, ans);
-            }
-        }
+        let mut run = || cache::run_day(&mut cache, cache_mode, *day, args.part, &solve);
+        let result = if args.visualize {
+            let (result, frames) = visualize::capture(run);
+            show_frames(&args, *day, &frames);
+            result
+        } else {
+            run()
+        };
+        let expected = stored_answers.get(day);
+        report_day(&result, args.verify.then_some(expected));
 
-        if !result.passed {
+        let passed = result.passed(expected);
+        if !passed {
             all_passed = false;
         }
+        if args.verify {
+            test_cases.extend(junit::cases(&result, args.part, expected));
+        }
+        let fingerprints = history::day_fingerprints(*day);
+        if let Some(export) = &mut export {
+            export.push(&result, fingerprints.map(|prints| prints.input));
+        }
+        history.record(
+            &result,
+            args.part,
+            args.verify.then_some(passed),
+            fingerprints,
+        );
+
+        if args.save && result.answers().next().is_some() {
+            // Update existing answers based on what we ran
+            let ans = stored_answers.entry(*day).or_default();
+            for (part, sol) in result.answers() {
+                ans.record(part, sol);
+            }
+        }
     }
 
     if days.len() > 1 {
@@ -189,8 +462,36 @@ fn main() {
         println!("\n=== Total: {:.4} ms ===", total_elapsed);
     }
 
+    if args.verify {
+        let summary: Summary = test_cases.iter().collect();
+        println!("\n{}", summary);
+    }
+    if let Some(path) = &args.junit
+        && let Err(err) = junit::write(path, &test_cases)
+    {
+        eprintln!("Error: cannot write {}: {}", path.display(), err);
+        std::process::exit(1);
+    }
+
+    if let (Some(path), Some(export)) = (&args.export, &export)
+        && let Err(err) = export.save(path)
+    {
+        eprintln!("Error: cannot write {}: {}", path.display(), err);
+        std::process::exit(1);
+    }
+
+    if cache_mode != CacheMode::Off
+        && let Err(err) = cache.save(CACHE_FILE)
+    {
+        eprintln!("Warning: cannot write {}: {}", CACHE_FILE, err);
+    }
+
+    if let Err(err) = history.save(HISTORY_FILE) {
+        eprintln!("Warning: cannot write {}: {}", HISTORY_FILE, err);
+    }
+
     if args.save {
-        save_answers(&stored_answers);
+        save_answers(ANSWERS_FILE, &stored_answers).expect("Failed to write answers file");
         println!("\nAnswers saved to {}", ANSWERS_FILE);
     }
 
@@ -199,82 +500,447 @@ fn main() {
     }
 }
 
-struct DayResult {
-    part1: Option<Solution>,
-    part2: Option<Solution>,
-    passed: bool,
+/// How the run solves days: in process, or in a limited child process with `--sandbox`
+fn solver(args: &Args) -> impl Fn(u8, &str, Option<u8>) -> DayResult {
+    #[cfg(unix)]
+    let limits = args.sandbox.then(|| sandbox::Limits {
+        memory: Some(args.memory_limit << 20),
+        cpu: Some(Duration::from_secs(args.cpu_limit)),
+    });
+    #[cfg(not(unix))]
+    if args.sandbox {
+        eprintln!("Error: --sandbox is only supported on Unix");
+        std::process::exit(1);
+    }
+
+    #[cfg(unix)]
+    if limits.is_some()
+        && let Err(message) = sandbox::check()
+    {
+        eprintln!("Error: {}", message);
+        std::process::exit(1);
+    }
+
+    move |day, input, part| {
+        #[cfg(unix)]
+        if let Some(limits) = limits {
+            return sandbox::solve(day, input, part, limits);
+        }
+        runner::solve(day, input, part)
+    }
 }
 
-fn run_day(day: u8, part: Option<u8>, verify: bool, expected: Option<&Answers>) -> DayResult {
-    let input = match fs::read_to_string(format!("./input/day{}/input.txt", day)) {
-        Ok(i) => i,
-        Err(_) => {
-            println!("\n=== Day {:02} ===", day);
+/// Print a day's results. `verify` holds the stored answers when verifying.
+fn report_day(result: &DayResult, verify: Option<Option<&Answers>>) {
+    println!("\n=== Day {:02} ===", result.day);
+
+    match &result.outcome {
+        Outcome::Skipped => {
             println!("  · Skipped (no input file)");
-            return DayResult {
-                part1: None,
-                part2: None,
-                passed: true,
-            };
+            return;
         }
-    };
+        Outcome::InvalidInput(errors) => {
+            println!("  · Invalid input ({} problems)", errors.len());
+            for err in errors.iter().take(MAX_VIOLATIONS_SHOWN) {
+                print_error(err);
+            }
+            if errors.len() > MAX_VIOLATIONS_SHOWN {
+                println!("      ... and {} more", errors.len() - MAX_VIOLATIONS_SHOWN);
+            }
+            return;
+        }
+        Outcome::Failed(err) => {
+            println!("  · Failed");
+            print_error(err);
+        }
+        Outcome::Solved { .. } => {
+            for (part, sol) in result.answers() {
+                report_part(part, sol, verify);
+            }
+        }
+    }
 
-    let solvers = get_day_solvers(day);
+    if result.cached {
+        println!("  · Elapsed: {:.4} ms (cached)", result.elapsed_ms());
+    } else {
+        println!("  · Elapsed: {:.4} ms", result.elapsed_ms());
+    }
+}
 
-    let start = Instant::now();
+fn report_part(part: u8, sol: &Solution, verify: Option<Option<&Answers>>) {
+    match verify {
+        Some(Some(exp)) => {
+            let ok = exp.matches(part, sol);
+            let status = if ok { "ok" } else { "FAIL" };
+            println!("  · Part {}: {} [{}]", part, sol, status);
+            if !ok {
+                println!("           expected: {}", exp.part(part));
+            }
+        }
+        Some(None) => println!("  · Part {}: {} [no expected answer]", part, sol),
+        None => println!("  · Part {}: {}", part, sol),
+    }
+    print_picture(sol);
+}
 
-    let (p1, p2) = match part {
-        Some(1) => (Some((solvers.part1)(&input)), None),
-        Some(2) => (None, Some((solvers.part2)(&input))),
-        _ => {
-            let (a, b) = (solvers.both)(&input);
-            (Some(a), Some(b))
+/// Inputs of `days`, and the days without one. Exits if an input can't be read.
+fn read_inputs(days: &[u8]) -> (Vec<(u8, String)>, Vec<u8>) {
+    let mut inputs = Vec::new();
+    let mut skipped = Vec::new();
+    for &day in days {
+        match runner::read_input(day) {
+            Ok(Some(input)) => inputs.push((day, input)),
+            Ok(None) => skipped.push(day),
+            Err(err) => {
+                eprintln!("Error: cannot read input of day {}: {}", day, err);
+                std::process::exit(1);
+            }
         }
-    };
+    }
+    (inputs, skipped)
+}
 
-    let elapsed_ms = start.elapsed().as_nanos() as f64 / 1_000_000.0;
+/// Solve each part (or only `part`) of `days` `runs` times and print the parts whose answers
+/// vary. Returns whether every part was stable.
+fn run_determinism(days: &[u8], part: Option<u8>, runs: usize) -> bool {
+    let (inputs, skipped) = read_inputs(days);
+    let report = determinism::Report::run(&inputs, part, runs);
+    print!("{}", report.render(&skipped));
+    report.stable()
+}
 
-    println!("\n=== Day {:02} ===", day);
+/// Compare each part (or only `part`) of `days` with the oracle from the command line or
+/// aoc.toml and print the mismatches and relative speed. Returns whether every compared part
+/// agreed.
+fn run_oracle(days: &[u8], part: Option<u8>, cli: Option<&str>) -> bool {
+    let config = Config::load(CONFIG_FILE).unwrap_or_else(|err| {
+        eprintln!("Error: cannot read {}: {}", CONFIG_FILE, err);
+        std::process::exit(1);
+    });
+    let (inputs, skipped) = read_inputs(days);
+    let report = oracle::Report::run(&inputs, part, cli, &config.oracle).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    });
+    print!("{}", report.render(&skipped));
+    report.agrees()
+}
+
+/// Benchmark `days` against the budget and print the report. Returns whether every day ran
+/// and the total fits the budget.
+fn run_budget(days: &[u8], total: Duration) -> bool {
+    let config = Config::load(CONFIG_FILE).unwrap_or_else(|err| {
+        eprintln!("Error: cannot read {}: {}", CONFIG_FILE, err);
+        std::process::exit(1);
+    });
+    let (inputs, skipped) = read_inputs(days);
 
-    let mut passed = true;
+    let report = budget::Report::run(&inputs, total, &config.budget.weights);
+    print!("{}", report.render(&skipped));
+    report.within_budget()
+}
 
-    if let Some(ref sol) = p1 {
-        if verify {
-            if let Some(exp) = expected {
-                let ok = sol.to_string() == exp.part1;
-                let status = if ok { "ok" } else { "FAIL" };
-                println!("  · Part 1: {} [{}]", sol, status);
-                if !ok {
-                    println!("           expected: {}", exp.part1);
-                    passed = false;
+fn run_command(command: &Command) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Command::Encrypt { days } => {
+            let key = Key::load()?;
+            for path in stored_files(days, |path| path.exists()) {
+                let encrypted = crypt::encrypt_file(&key, &path)?;
+                println!("Encrypted {} -> {}", path.display(), encrypted.display());
+            }
+        }
+        Command::Decrypt { days, force } => {
+            let key = Key::load()?;
+            for path in stored_files(days, |path| crypt::encrypted_path(path).exists()) {
+                if path.exists() && !force {
+                    println!("Skipped {} (exists, use --force)", path.display());
+                    continue;
                 }
-            } else {
-                println!("  · Part 1: {} [no expected answer]", sol);
+                crypt::decrypt_file(&key, &path)?;
+                println!("Decrypted {}", path.display());
             }
-        } else {
-            println!("  · Part 1: {}", sol);
         }
-    }
-
-    if let Some(ref sol) = p2 {
-        if verify {
-            if let Some(exp) = expected {
-                let ok = sol.to_string() == exp.part2;
-                let status = if ok { "ok" } else { "FAIL" };
-                println!("  · Part 2: {} [{}]", sol, status);
-                if !ok {
-                    println!("           expected: {}", exp.part2);
-                    passed = false;
+        Command::Keygen => {
+            let path = crypt::key_file();
+            Key::generate().save(&path)?;
+            println!("Wrote new key to {}", path.display());
+        }
+        Command::Inspect { day } => {
+            let Some(input) = runner::read_input(*day)? else {
+                let path = runner::input_path(*day);
+                return Err(format!("no input for day {} at {}", day, path.display()).into());
+            };
+            print!("{}", inspect::inspect(&input).render(*day));
+        }
+        Command::Shrink {
+            day,
+            input,
+            part,
+            split,
+            oracle,
+            predicate,
+        } => {
+            let text = read_command_input(*day, input.as_deref())?;
+            let config = Config::load(CONFIG_FILE)?;
+            let timeout = oracle::timeout(&config.oracle);
+            let predicate = match (split, oracle, predicate) {
+                (true, _, _) => Predicate::Split,
+                (_, Some(template), _) => {
+                    Predicate::oracle(template.as_deref(), &config.oracle, *day)
+                        .ok_or_else(|| format!("no oracle command for day {}", day))?
                 }
-            } else {
-                println!("  · Part 2: {} [no expected answer]", sol);
+                (_, _, Some(command)) => Predicate::Command(command.clone()),
+                _ => unreachable!("clap requires a predicate"),
+            };
+            let shrunk = predicate.shrink(*day, *part, &text, timeout)?;
+            let path = shrunk.save(*day)?;
+            print!("{}", shrunk.render(*day, &text, &path));
+        }
+        Command::Anonymize { day, seed, input } => {
+            let text = read_command_input(*day, input.as_deref())?;
+            let fixture = anonymize::fixture(*day, &text, *seed)?;
+            anonymize::save(*day, &fixture)?;
+            println!(
+                "Anonymized day {} input ({} lines, seed {}) into {}",
+                day,
+                fixture.input.lines().count(),
+                fixture.seed,
+                anonymize::fixture_path(*day).display()
+            );
+            println!(
+                "Recorded answers {} and {} in {}",
+                fixture.answers.part1,
+                fixture.answers.part2,
+                anonymize::fixture_answers_path().display()
+            );
+        }
+        Command::Compare { files } => {
+            let exports = files
+                .iter()
+                .map(Export::load)
+                .collect::<Result<Vec<_>, _>>()?;
+            let comparison = compare::compare(&exports);
+            print!("{}", compare::render(&exports, &comparison));
+            if !comparison.disagreements.is_empty() {
+                return Err(format!(
+                    "answers differ on shared inputs ({} parts)",
+                    comparison.disagreements.len()
+                )
+                .into());
+            }
+        }
+        Command::Generate {
+            day,
+            scale,
+            seed,
+            output,
+        } => {
+            let input = generate::generate(*day, *scale, *seed)
+                .ok_or_else(|| format!("day {} has no input generator", day))?;
+            match output {
+                Some(path) => fs::write(path, input)?,
+                None => print!("{}", input),
             }
-        } else {
-            println!("  · Part 2: {}", sol);
         }
+        Command::Scale {
+            day,
+            max_scale,
+            steps,
+            seed,
+        } => {
+            let scales = scale::scales(*max_scale, *steps as usize);
+            let report = scale::Report::run(*day, &scales, *seed)
+                .ok_or_else(|| format!("day {} has no input generator", day))?;
+            print!("{}", report.render());
+            if report.fit().is_none() {
+                return Err("too few successful runs to fit a curve".into());
+            }
+        }
+        Command::Leaderboard {
+            file,
+            url,
+            me,
+            refresh,
+        } => {
+            let config = Config::load(CONFIG_FILE)?.leaderboard;
+            let text = match (file, url.as_ref().or(config.url.as_ref())) {
+                (Some(path), _) => fs::read_to_string(path)
+                    .map_err(|err| format!("cannot read {}: {}", path.display(), err))?,
+                (None, Some(url)) => leaderboard::fetch(url, *refresh)?,
+                (None, None) => {
+                    return Err(
+                        "no leaderboard: pass --file or --url, or set [leaderboard] url in aoc.toml"
+                            .into(),
+                    );
+                }
+            };
+            let board = Leaderboard::parse(&text)?;
+            print!("{}", board.render());
+
+            if let Some(name) = me.as_ref().or(config.me.as_ref()) {
+                let member = board
+                    .member(name)
+                    .ok_or_else(|| format!("no member {:?} on the leaderboard", name))?;
+                let answers = load_answers(ANSWERS_FILE)?;
+                print!("{}", board.render_own_stars(member, &answers));
+            }
+        }
+        Command::Read {
+            day,
+            file,
+            url,
+            refresh,
+            extract,
+            plain,
+        } => {
+            let html = match file {
+                Some(path) => fs::read_to_string(path)
+                    .map_err(|err| format!("cannot read {}: {}", path.display(), err))?,
+                None => fetch::cached(&puzzle::cache_path(*day), None, *refresh, || {
+                    fetch::get(&puzzle::url(url, *day), fetch::session().as_deref())
+                })?,
+            };
+            let ansi = !plain && io::stdout().is_terminal();
+            print!("{}", puzzle::render(&html, ansi));
+            extract_examples(*day, &puzzle::examples(&html), extract.as_deref())?;
+        }
+        Command::Serve {
+            bind,
+            port,
+            max_body,
+            timeout,
+            max_solves,
+            allow_origin,
+        } => {
+            let defaults = ServerConfig::default();
+            let config = ServerConfig {
+                max_body: *max_body,
+                solve_timeout: Duration::from_millis(*timeout),
+                max_solves: max_solves.map_or(defaults.max_solves, |n| n as usize),
+                allow_origin: allow_origin.clone(),
+                ..defaults
+            };
+            let server = Server::bind((bind.as_str(), *port), config)?;
+            println!(
+                "Serving {} days on http://{}",
+                num_days(),
+                server.local_addr()?
+            );
+            server.run()?;
+        }
+    }
+    Ok(())
+}
+
+/// List the page's examples and save the chosen ones as new example files. Without a
+/// `choice` the user is asked, if there is one at the terminal.
+fn extract_examples(
+    day: u8,
+    examples: &[String],
+    choice: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if examples.is_empty() {
+        return Ok(());
+    }
+    print!("{}", puzzle::list_examples(day, examples));
+
+    let choice = match choice {
+        Some(choice) => choice.to_string(),
+        None if io::stdin().is_terminal() => {
+            print!(
+                "\nSave examples to input/day{}/ (like 1,3 or all, Enter for none): ",
+                day
+            );
+            io::stdout().flush()?;
+            let mut line = String::new();
+            io::stdin().read_line(&mut line)?;
+            line
+        }
+        None => {
+            println!("\nSave them with --extract, like --extract 1 or --extract all");
+            return Ok(());
+        }
+    };
+    let chosen = puzzle::select(&choice, examples.len())?;
+    print!("{}", puzzle::save_examples(day, examples, &chosen)?);
+    Ok(())
+}
+
+/// The input at `path`, or the day's puzzle input
+fn read_command_input(day: u8, path: Option<&Path>) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(match path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {}", path.display(), err))?,
+        None => runner::read_input(day)?.ok_or_else(|| {
+            let path = runner::input_path(day);
+            format!("no input for day {} at {}", day, path.display())
+        })?,
+    })
+}
+
+/// Parse a finite, positive number
+fn positive(text: &str) -> Result<f64, String> {
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+        _ => Err(format!("expected a positive number, got {:?}", text)),
     }
+}
+
+/// Plaintext paths of the inputs for `days` (all days if empty) and answers.txt, keeping
+/// the ones that pass `filter`
+fn stored_files(days: &[u8], filter: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let days = match days {
+        [] => advent_2025::days()
+            .iter()
+            .map(|solvers| solvers.day)
+            .collect(),
+        days => days.to_vec(),
+    };
+    days.into_iter()
+        .map(runner::input_path)
+        .chain([PathBuf::from(ANSWERS_FILE)])
+        .filter(|path| filter(path))
+        .collect()
+}
+
+/// Play or export the frames recorded while running a day
+fn show_frames(args: &Args, day: u8, frames: &[Frame]) {
+    if frames.is_empty() {
+        return;
+    }
+
+    let shown = match &args.frames_dir {
+        Some(dir) => {
+            let dir = dir.join(format!("day{:02}", day));
+            visualize::export(frames, &dir).map(|_| format!("written to {}", dir.display()))
+        }
+        None => {
+            let playback = Playback {
+                fps: args.fps,
+                step: args.step,
+            };
+            visualize::play(frames, playback).map(|_| "played".to_string())
+        }
+    };
 
-    println!("  · Elapsed: {:.4} ms", elapsed_ms);
+    match shown {
+        Ok(how) => println!("\nDay {:02}: {} frames {}", day, frames.len(), how),
+        Err(err) => eprintln!("\nDay {:02}: failed to show frames: {}", day, err),
+    }
+}
 
-    DayResult { part1: p1, part2: p2, passed }
+/// Print a rendered error, indented under the day's output
+fn print_error(err: &SolveError) {
+    for line in err.to_string().lines() {
+        println!("      {}", line);
+    }
+}
+
+/// Print the drawing of a picture answer under its part line
+fn print_picture(sol: &Solution) {
+    if let Some(picture) = sol.picture() {
+        for line in picture.lines() {
+            println!("      {}", line);
+        }
+    }
 }
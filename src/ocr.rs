@@ -0,0 +1,219 @@
+use crate::grid::Grid;
+
+/// The AoC 4x6 letter font. Letters are separated by blank columns, most are 4 wide
+/// but `I` and `Y` aren't, so glyphs are matched on their lit columns only.
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The AoC 6x10 letter font
+const FONT_10: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Parse a '#'/'.' drawing into a picture.
+pub fn parse_picture(drawing: &str) -> Grid<bool> {
+    Grid::from_rows(
+        drawing
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect(),
+    )
+}
+
+/// Read the letters drawn in a picture using the standard AoC fonts.
+/// Blank rows and columns around the text are ignored. Returns None if the height doesn't
+/// match a known font or any letter isn't recognized.
+pub fn recognize(picture: &Grid<bool>) -> Option<String> {
+    let lit_row = |y: usize| picture.iter_row(y).any(|&lit| lit);
+    let top = (0..picture.height()).find(|&y| lit_row(y))?;
+    let bottom = (0..picture.height()).rfind(|&y| lit_row(y))?;
+
+    let font = match bottom - top + 1 {
+        6 => FONT_6,
+        10 => FONT_10,
+        _ => return None,
+    };
+
+    // columns of the text, each column as a bitmask of lit rows
+    let columns: Vec<u16> = (0..picture.width())
+        .map(|x| column_mask((top..=bottom).map(|y| picture[(x, y)])))
+        .collect();
+
+    letters(&columns)
+        .map(|letter| {
+            font.iter()
+                .find(|(_, glyph)| glyph_columns(glyph) == letter)
+                .map(|&(c, _)| c)
+        })
+        .collect()
+}
+
+fn column_mask(column: impl Iterator<Item = bool>) -> u16 {
+    column
+        .enumerate()
+        .fold(0, |mask, (y, lit)| mask | (u16::from(lit) << y))
+}
+
+/// Split columns into letters at blank columns
+fn letters(columns: &[u16]) -> impl Iterator<Item = &[u16]> {
+    columns
+        .split(|&mask| mask == 0)
+        .filter(|letter| !letter.is_empty())
+}
+
+/// Lit columns of a glyph drawing, as bitmasks
+fn glyph_columns(glyph: &str) -> Vec<u16> {
+    let picture = parse_picture(glyph);
+    let columns: Vec<u16> = picture
+        .iter_cols()
+        .map(|col| column_mask(col.copied()))
+        .collect();
+    letters(&columns).flatten().copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draw text in the given font with a single blank column between letters
+    fn draw(font: &[(char, &str)], text: &str) -> Grid<bool> {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| {
+                let (_, glyph) = font.iter().find(|(g, _)| *g == c).unwrap();
+                glyph.lines().collect()
+            })
+            .collect();
+        let height = glyphs[0].len();
+        let drawing: Vec<String> = (0..height)
+            .map(|y| glyphs.iter().map(|g| g[y]).collect::<Vec<_>>().join("."))
+            .collect();
+        parse_picture(&drawing.join("\n"))
+    }
+
+    #[test]
+    fn test_recognize_small_font() {
+        let picture = parse_picture(
+            "\
+#..#.###.
+#..#..#..
+####..#..
+#..#..#..
+#..#..#..
+#..#.###.",
+        );
+        assert_eq!(recognize(&picture).as_deref(), Some("HI"));
+
+        let alphabet: String = FONT_6.iter().map(|&(c, _)| c).collect();
+        assert_eq!(recognize(&draw(FONT_6, &alphabet)), Some(alphabet));
+    }
+
+    #[test]
+    fn test_recognize_large_font() {
+        let alphabet: String = FONT_10.iter().map(|&(c, _)| c).collect();
+        assert_eq!(recognize(&draw(FONT_10, &alphabet)), Some(alphabet));
+    }
+
+    #[test]
+    fn test_recognize_with_border() {
+        let picture = parse_picture(
+            "\
+..........
+..####....
+..#.......
+..###.....
+..#.......
+..#.......
+..####....
+..........",
+        );
+        assert_eq!(recognize(&picture).as_deref(), Some("E"));
+    }
+
+    #[test]
+    fn test_unrecognized() {
+        // wrong height
+        assert_eq!(recognize(&parse_picture("#\n#\n#")), None);
+        // right height, unknown glyph
+        assert_eq!(recognize(&parse_picture("#\n#\n#\n#\n#\n#")), None);
+        // nothing drawn
+        assert_eq!(recognize(&parse_picture("....\n....")), None);
+    }
+}
@@ -0,0 +1,560 @@
+//! Cross-checking answers against an external program, like a teammate's solution in another
+//! language. The program is run through the shell once per day and part with the day's input,
+//! and what it prints is compared with our answer.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::OracleConfig;
+use crate::runner;
+use crate::select::DaySet;
+use crate::solution::Solution;
+
+/// How long an oracle may run before it is killed, unless the config says otherwise
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Command template for a day: the one given on the command line, else the day's entry in
+/// the config, else the config's default. None if the day has no oracle.
+pub fn template<'a>(cli: Option<&'a str>, config: &'a OracleConfig, day: u8) -> Option<&'a str> {
+    cli.or_else(|| config.days.get(&day).map(String::as_str))
+        .or(config.command.as_deref())
+}
+
+/// How long oracles may run, from the config
+pub fn timeout(config: &OracleConfig) -> Duration {
+    config
+        .timeout
+        .map_or(DEFAULT_TIMEOUT, |secs| Duration::from_secs(secs.get()))
+}
+
+/// Fill in a command template: `{day}`, `{part}` and `{input}`, the path of the input file
+/// quoted for the shell
+pub fn expand(template: &str, day: u8, part: u8, input: &Path) -> String {
+    template
+        .replace("{day}", &day.to_string())
+        .replace("{part}", &part.to_string())
+        .replace("{input}", &quote(&input.display().to_string()))
+}
+
+/// Quote `text` as a single word for `sh`
+#[cfg(unix)]
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Quote `text` as a single word for `cmd`, which has no way to escape a `"` in a path, but
+/// paths can't contain one there
+#[cfg(windows)]
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text)
+}
+
+/// The input as a file for the oracle: the plaintext input if there is one, otherwise a
+/// temporary copy of the decrypted input that is removed on drop
+#[derive(Debug)]
+pub struct InputFile {
+    path: PathBuf,
+    temporary: bool,
+}
+
+impl InputFile {
+    pub fn new(day: u8, input: &str) -> io::Result<Self> {
+        let path = runner::input_path(day);
+        if path.exists() {
+            return Ok(Self {
+                path,
+                temporary: false,
+            });
+        }
+        Self::temporary(day, input)
+    }
+
+    /// A temporary file holding `input`, even if the day has a plaintext input
+    pub fn temporary(day: u8, input: &str) -> io::Result<Self> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let name = format!(
+            "advent-2025-{}-{}-day{}.txt",
+            std::process::id(),
+            count,
+            day
+        );
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, input)?;
+        Ok(Self {
+            path,
+            temporary: true,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for InputFile {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// What an oracle printed, or why it failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct OracleRun {
+    /// Trimmed stdout, or the error with the last line of stderr
+    pub output: Result<String, String>,
+    /// Wall time including starting the process
+    pub elapsed: Duration,
+}
+
+/// The shell running `command`, in a process group of its own on Unix so that whatever it
+/// starts can be killed along with it
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    use std::os::unix::process::CommandExt;
+
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command).process_group(0);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+/// Run a shell command with `input` on its stdin, killing it after `timeout`
+pub fn run(command: &str, input: &str, timeout: Duration) -> OracleRun {
+    let start = Instant::now();
+    let output = run_command(command, input, timeout, start);
+    OracleRun {
+        output,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn run_command(
+    command: &str,
+    input: &str,
+    timeout: Duration,
+    start: Instant,
+) -> Result<String, String> {
+    let mut child = shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("cannot run oracle: {}", err))?;
+
+    // fed and drained on their own threads, so a full pipe can't block the oracle
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_string();
+    // oracles reading the input file may exit without reading stdin, that's fine
+    let writer = thread::spawn(move || drop(stdin.write_all(input.as_bytes())));
+    let stdout = drain(child.stdout.take().expect("stdout is piped"));
+    let stderr = drain(child.stderr.take().expect("stderr is piped"));
+
+    let status = wait(&mut child, timeout, start)?;
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        let last_line = stderr.lines().rev().find(|line| !line.trim().is_empty());
+        return Err(match last_line {
+            Some(line) => format!("oracle {}: {}", status, line.trim()),
+            None => format!("oracle {}", status),
+        });
+    }
+    Ok(stdout.trim().to_string())
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+fn wait(
+    child: &mut Child,
+    timeout: Duration,
+    start: Instant,
+) -> Result<std::process::ExitStatus, String> {
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) if start.elapsed() < timeout => thread::sleep(Duration::from_millis(1)),
+            Ok(None) => {
+                kill(child);
+                let _ = child.wait();
+                return Err(format!("oracle timed out after {:?}", timeout));
+            }
+            Err(err) => return Err(format!("cannot wait for oracle: {}", err)),
+        }
+    }
+}
+
+/// Kill the oracle's process group, so that processes it started don't outlive it holding
+/// its pipes open
+#[cfg(unix)]
+fn kill(child: &mut Child) {
+    // SAFETY: kill has no memory safety requirements; the group is the child's own, see `shell`
+    if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } != 0 {
+        let _ = child.kill();
+    }
+}
+
+#[cfg(windows)]
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+}
+
+/// Our answer to a part next to the oracle's
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    /// Our answer, or why there is none
+    pub ours: Result<Solution, String>,
+    pub elapsed: Duration,
+    pub oracle: OracleRun,
+}
+
+impl Comparison {
+    /// Whether both gave an answer and they match, comparing typed values like `--verify`
+    pub fn agrees(&self) -> bool {
+        match (&self.ours, &self.oracle.output) {
+            (Ok(ours), Ok(theirs)) => {
+                let Ok(theirs) = theirs.parse::<Solution>();
+                ours.matches(&theirs)
+            }
+            _ => false,
+        }
+    }
+
+    /// How many times faster we are than the oracle, None unless both gave an answer
+    pub fn speedup(&self) -> Option<f64> {
+        let ours = self.elapsed.as_secs_f64();
+        (self.ours.is_ok() && self.oracle.output.is_ok() && ours > 0.0)
+            .then(|| self.oracle.elapsed.as_secs_f64() / ours)
+    }
+}
+
+/// Solve `part` of `day` and run the oracle `template` on the same input, killing it after
+/// `timeout`
+pub fn compare(
+    day: u8,
+    part: u8,
+    input: &InputFile,
+    text: &str,
+    template: &str,
+    timeout: Duration,
+) -> Comparison {
+    let result = runner::solve(day, text, Some(part));
+    let command = expand(template, day, part, input.path());
+    Comparison {
+        day,
+        part,
+        ours: result.answer(part).cloned(),
+        elapsed: result.elapsed,
+        oracle: run(&command, text, timeout),
+    }
+}
+
+/// The comparisons of several days, as run by `--oracle`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// The days compared, with the oracle command template used and each part's comparison
+    pub days: Vec<(u8, String, Vec<Comparison>)>,
+    /// Days without an oracle command
+    pub no_oracle: Vec<u8>,
+}
+
+impl Report {
+    /// Compare each part (or only `part`) of `days` (with their inputs) with the oracle from
+    /// the command line or the config. Fails if an input can't be written for the oracle.
+    pub fn run(
+        days: &[(u8, String)],
+        part: Option<u8>,
+        cli: Option<&str>,
+        config: &OracleConfig,
+    ) -> Result<Self, String> {
+        let parts = match part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+        let timeout = timeout(config);
+        let mut report = Report {
+            days: Vec::new(),
+            no_oracle: Vec::new(),
+        };
+        for (day, input) in days {
+            let Some(template) = template(cli, config, *day) else {
+                report.no_oracle.push(*day);
+                continue;
+            };
+            let file = InputFile::new(*day, input).map_err(|err| {
+                format!("cannot write input of day {} for the oracle: {}", day, err)
+            })?;
+            let comparisons = parts
+                .iter()
+                .map(|&part| compare(*day, part, &file, input, template, timeout))
+                .collect();
+            report.days.push((*day, template.to_string(), comparisons));
+        }
+        Ok(report)
+    }
+
+    /// Parts that don't agree with the oracle, as `day.part`
+    pub fn disagreeing(&self) -> Vec<String> {
+        self.days
+            .iter()
+            .flat_map(|(_, _, comparisons)| comparisons)
+            .filter(|comparison| !comparison.agrees())
+            .map(|comparison| format!("{}.{}", comparison.day, comparison.part))
+            .collect()
+    }
+
+    /// Whether every compared part agrees with the oracle
+    pub fn agrees(&self) -> bool {
+        self.disagreeing().is_empty()
+    }
+
+    /// The report as printed by `--oracle`: each part's answer and relative speed, or both
+    /// answers on a mismatch, the `skipped` days without an input, the days without an
+    /// oracle, and the parts that disagree
+    pub fn render(&self, skipped: &[u8]) -> String {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let mut text = String::new();
+        for (day, template, comparisons) in &self.days {
+            text += &format!("\n=== Day {:02} === ({})\n", day, template);
+            for comparison in comparisons {
+                let part = comparison.part;
+                let ours = match &comparison.ours {
+                    Ok(sol) => sol.to_string(),
+                    Err(message) => format!("error: {}", message),
+                };
+                let timing = format!(
+                    "{:.4} ms, oracle {:.4} ms",
+                    ms(comparison.elapsed),
+                    ms(comparison.oracle.elapsed)
+                );
+                match (comparison.agrees(), comparison.speedup()) {
+                    (true, Some(speedup)) if speedup >= 1.0 => {
+                        text += &format!(
+                            "  · Part {}: {} [agrees] {}, {:.1}x faster\n",
+                            part, ours, timing, speedup
+                        )
+                    }
+                    (true, Some(speedup)) => {
+                        text += &format!(
+                            "  · Part {}: {} [agrees] {}, {:.1}x slower\n",
+                            part,
+                            ours,
+                            timing,
+                            1.0 / speedup
+                        )
+                    }
+                    _ => {
+                        text += &format!("  · Part {}: {} [MISMATCH] {}\n", part, ours, timing);
+                        text += &match &comparison.oracle.output {
+                            Ok(theirs) => format!("           oracle: {}\n", theirs),
+                            Err(message) => format!("           oracle failed: {}\n", message),
+                        };
+                    }
+                }
+            }
+        }
+        if !skipped.is_empty() {
+            let skipped: DaySet = skipped.iter().copied().collect();
+            text += &format!("\n  Skipped (no input): {}\n", skipped);
+        }
+        if !self.no_oracle.is_empty() {
+            let no_oracle: DaySet = self.no_oracle.iter().copied().collect();
+            text += &format!("\n  No oracle command: {}\n", no_oracle);
+        }
+
+        let disagreeing = self.disagreeing();
+        if disagreeing.is_empty() {
+            text += "\n  All compared parts agree with the oracle\n";
+        } else {
+            text += &format!(
+                "\n  Parts disagreeing with the oracle: {}\n",
+                disagreeing.join(", ")
+            );
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn test_template() {
+        let config = OracleConfig {
+            command: Some("python3 day{day}.py".into()),
+            days: BTreeMap::from([(7, "./day7 {input} {part}".into())]),
+            timeout: None,
+        };
+        assert_eq!(template(None, &config, 1), Some("python3 day{day}.py"));
+        assert_eq!(template(None, &config, 7), Some("./day7 {input} {part}"));
+        assert_eq!(template(Some("x"), &config, 7), Some("x"));
+        assert_eq!(template(None, &OracleConfig::default(), 7), None);
+
+        let path = Path::new("input/day7/input.txt");
+        assert_eq!(
+            expand("./day{day} {input} {part}", 7, 2, path),
+            format!("./day7 {} 2", quote("input/day7/input.txt"))
+        );
+        assert_eq!(timeout(&config), DEFAULT_TIMEOUT);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_quoting() {
+        let dir = std::env::temp_dir().join(format!("oracle 'quoted' $(x) {}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("day 1.txt");
+        fs::write(&path, "42\n").unwrap();
+        let command = expand("cat {input}", 1, 1, &path);
+        assert_eq!(run(&command, "", DEFAULT_TIMEOUT).output, Ok("42".into()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run() {
+        let echo = run("cat", "  42\n", DEFAULT_TIMEOUT);
+        assert_eq!(echo.output, Ok("42".into()));
+
+        let failed = run("echo oops >&2; exit 3", "", DEFAULT_TIMEOUT);
+        let message = failed.output.unwrap_err();
+        assert!(
+            message.contains("3") && message.ends_with("oops"),
+            "{}",
+            message
+        );
+
+        let slow = run("sleep 5", "", Duration::from_millis(50));
+        assert!(slow.output.unwrap_err().contains("timed out"));
+        assert!(slow.elapsed < Duration::from_secs(5));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_orphans() {
+        // a process the oracle started, which must be killed with it
+        let pid_file = std::env::temp_dir().join(format!("oracle-{}.pid", std::process::id()));
+        let pid_path = quote(&pid_file.display().to_string());
+        let command = format!("sleep 30 & echo $! > {}; wait", pid_path);
+        let orphaning = run(&command, "", Duration::from_millis(200));
+        assert!(orphaning.output.unwrap_err().contains("timed out"));
+        let pid = fs::read_to_string(&pid_file).unwrap().trim().to_string();
+        fs::remove_file(&pid_file).unwrap();
+
+        // gone, or a zombie waiting for init to reap it
+        let alive = || {
+            let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap_or_default();
+            let state = stat.rsplit_once(") ").map(|(_, rest)| &rest[..1]);
+            state.is_some_and(|state| state != "Z")
+        };
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while alive() {
+            assert!(
+                Instant::now() < deadline,
+                "sleep {} outlived the oracle",
+                pid
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_compare() {
+        // a day without a plaintext input, so the file is a temporary copy
+        let input = InputFile::new(25, DAY1_INPUT).unwrap();
+        assert_eq!(fs::read_to_string(input.path()).unwrap(), DAY1_INPUT);
+
+        let agreeing = compare(1, 1, &input, DAY1_INPUT, "echo 3", DEFAULT_TIMEOUT);
+        assert!(agreeing.agrees());
+        assert!(agreeing.speedup().is_some());
+
+        let template = "test $(wc -l < {input}) = 9 && echo {day}{part}";
+        let mismatch = compare(1, 2, &input, DAY1_INPUT, template, DEFAULT_TIMEOUT);
+        assert_eq!(mismatch.oracle.output, Ok("12".into()));
+        assert!(!mismatch.agrees());
+
+        let path = input.path().to_path_buf();
+        drop(input);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_render() {
+        let ms = Duration::from_millis;
+        let comparison = |part, ours: u64, theirs: Result<&str, &str>| Comparison {
+            day: 1,
+            part,
+            ours: Ok(Solution::U64(ours)),
+            elapsed: ms(2),
+            oracle: OracleRun {
+                output: theirs.map(str::to_string).map_err(str::to_string),
+                elapsed: ms(10),
+            },
+        };
+        let report = Report {
+            days: vec![(
+                1,
+                "python3 day{day}.py".into(),
+                vec![comparison(1, 3, Ok("3")), comparison(2, 6, Ok("7"))],
+            )],
+            no_oracle: vec![4, 5],
+        };
+        let expected = "
+=== Day 01 === (python3 day{day}.py)
+  · Part 1: 3 [agrees] 2.0000 ms, oracle 10.0000 ms, 5.0x faster
+  · Part 2: 6 [MISMATCH] 2.0000 ms, oracle 10.0000 ms
+           oracle: 7
+
+  Skipped (no input): 2
+
+  No oracle command: 4-5
+
+  Parts disagreeing with the oracle: 1.2
+";
+        assert_eq!(report.render(&[2]), expected);
+        assert!(!report.agrees());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_report() {
+        let config = OracleConfig {
+            command: None,
+            days: BTreeMap::from([(1, "echo 3".into())]),
+            timeout: None,
+        };
+        let days = [(1, DAY1_INPUT.to_string()), (2, String::new())];
+        let report = Report::run(&days, Some(1), None, &config).unwrap();
+        assert_eq!(report.no_oracle, [2]);
+        assert_eq!(report.days.len(), 1);
+        assert!(report.agrees());
+    }
+}
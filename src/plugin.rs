@@ -0,0 +1,397 @@
+//! Day solvers loaded at runtime from shared libraries, so experimental days can be shipped
+//! without touching `register_days!` or relinking the runner.
+//!
+//! A plugin is a `cdylib` in the plugins directory exporting [`ENTRY_POINT`], which returns a
+//! pointer to a static [`PluginDay`] with the day number and a C function per part. Answers
+//! and errors cross the boundary as NUL-terminated strings owned by the plugin. Rust plugins
+//! implement [`Day`](crate::solution::Day) as usual and let [`export_plugin!`] generate the
+//! C side:
+//!
+//! ```ignore
+//! advent_2025::export_plugin!(12, Solution);
+//! ```
+//!
+//! Loaded days join the registry next to the built-in ones. Libraries are never unloaded.
+
+use std::ffi::{CStr, CString, c_char};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use libloading::Library;
+
+use crate::error::{self, SolveError};
+use crate::history::fingerprint;
+use crate::schema::Schema;
+use crate::solution::{Solution, SolveResult};
+use crate::{DaySolvers, SOLVERS};
+
+/// Default plugins directory, relative to the working directory
+pub const PLUGINS_DIR: &str = "plugins";
+/// Version of [`PluginDay`], bumped on any layout or calling convention change
+pub const ABI_VERSION: u32 = 1;
+/// Symbol every plugin exports, an [`EntryPoint`]
+pub const ENTRY_POINT: &str = "aoc_plugin_register";
+
+/// Solve one part. `input` points to `len` bytes of UTF-8. Returns 0 and stores the answer in
+/// `*out`, or nonzero and stores an error message (or null) in `*out`. Strings stored in `*out`
+/// are released by the runner through [`PluginDay::free`].
+pub type PluginPart =
+    unsafe extern "C" fn(input: *const u8, len: usize, out: *mut *mut c_char) -> i32;
+
+/// Returns a pointer to the plugin's static registration
+pub type EntryPoint = unsafe extern "C" fn() -> *const PluginDay;
+
+/// Registration of a plugin day. `abi_version` comes first so it can be checked before
+/// relying on the rest of the layout.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PluginDay {
+    pub abi_version: u32,
+    pub day: u32,
+    pub part1: PluginPart,
+    pub part2: PluginPart,
+    /// Frees a string returned through a part's `out`
+    pub free: unsafe extern "C" fn(*mut c_char),
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+    /// Reading the plugins directory failed
+    Io(io::Error),
+    /// The library can't be loaded or lacks the entry point
+    Load { path: PathBuf, message: String },
+    /// Built against another version of [`PluginDay`]
+    Abi { path: PathBuf, version: u32 },
+    /// The day number is outside 1-25
+    InvalidDay { path: PathBuf, day: u32 },
+    /// The day is already built in or provided by another plugin
+    Duplicate { path: PathBuf, day: u8 },
+    /// Plugins were loaded after the registry was first used
+    RegistryInUse,
+}
+
+impl Display for PluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PluginError::Io(err) => write!(f, "cannot read plugins: {}", err),
+            PluginError::Load { path, message } => {
+                write!(f, "{}: cannot load plugin: {}", path.display(), message)
+            }
+            PluginError::Abi { path, version } => write!(
+                f,
+                "{}: plugin ABI version {} is not supported (expected {})",
+                path.display(),
+                version,
+                ABI_VERSION
+            ),
+            PluginError::InvalidDay { path, day } => {
+                write!(f, "{}: invalid day {}", path.display(), day)
+            }
+            PluginError::Duplicate { path, day } => {
+                write!(f, "{}: day {} is already registered", path.display(), day)
+            }
+            PluginError::RegistryInUse => {
+                write!(f, "plugins must be loaded before the registry is used")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
+/// Load every shared library in `dir` and add their days to the registry. A missing
+/// directory means no plugins. Plugins that fail to load are skipped and reported, the
+/// others are still registered.
+pub fn load(dir: &Path) -> Vec<PluginError> {
+    let paths = match library_paths(dir) {
+        Ok(paths) => paths,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(err) => return vec![PluginError::Io(err)],
+    };
+
+    let mut errors = Vec::new();
+    let mut loaded: Vec<DaySolvers> = Vec::new();
+    for path in paths {
+        match open(&path) {
+            Ok(solvers) if SOLVERS.iter().chain(&loaded).any(|s| s.day == solvers.day) => {
+                errors.push(PluginError::Duplicate {
+                    path,
+                    day: solvers.day,
+                });
+            }
+            Ok(solvers) => loaded.push(solvers),
+            Err(err) => errors.push(err),
+        }
+    }
+    if !loaded.is_empty() && crate::install_days(loaded).is_err() {
+        errors.push(PluginError::RegistryInUse);
+    }
+    errors
+}
+
+/// Shared libraries in `dir` for this platform, in name order
+fn library_paths(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Load a plugin library and wrap its day as registry solvers
+fn open(path: &Path) -> Result<DaySolvers, PluginError> {
+    let load_error = |message: String| PluginError::Load {
+        path: path.to_path_buf(),
+        message,
+    };
+    let contents = fs::read(path).map_err(|err| load_error(err.to_string()))?;
+
+    // SAFETY: loading a library runs its initializers, plugins are trusted code
+    let library = unsafe { Library::new(path) }.map_err(|err| load_error(err.to_string()))?;
+    // SAFETY: the entry point is declared with the `EntryPoint` signature
+    let registration = unsafe {
+        let entry = library
+            .get::<EntryPoint>(ENTRY_POINT.as_bytes())
+            .map_err(|err| load_error(err.to_string()))?;
+        entry()
+    };
+    if registration.is_null() {
+        return Err(load_error("entry point returned null".to_string()));
+    }
+    // SAFETY: the registration is static in the library, and every ABI version starts
+    // with `abi_version`
+    let version = unsafe { (*registration).abi_version };
+    if version != ABI_VERSION {
+        return Err(PluginError::Abi {
+            path: path.to_path_buf(),
+            version,
+        });
+    }
+    // SAFETY: same version, so the layout matches
+    let plugin = unsafe { *registration };
+    let day = match u8::try_from(plugin.day) {
+        Ok(day @ 1..=25) => day,
+        _ => {
+            return Err(PluginError::InvalidDay {
+                path: path.to_path_buf(),
+                day: plugin.day,
+            });
+        }
+    };
+
+    // the part functions live in the library, so it must stay loaded
+    std::mem::forget(library);
+    let source = format!("plugin {} {:016x}", path.display(), fingerprint(&contents));
+    Ok(solvers(day, plugin, Box::leak(source.into_boxed_str())))
+}
+
+/// Registry solvers calling into a plugin
+fn solvers(day: u8, plugin: PluginDay, source: &'static str) -> DaySolvers {
+    DaySolvers {
+        day,
+        source,
+        // plugins validate their own schema, see `run_part`
+        schema: &Schema::Any,
+        part1: Box::leak(Box::new(move |input: &str| {
+            call(&plugin, plugin.part1, input)
+        })),
+        part2: Box::leak(Box::new(move |input: &str| {
+            call(&plugin, plugin.part2, input)
+        })),
+        both: Box::leak(Box::new(move |input: &str| {
+            Ok((
+                call(&plugin, plugin.part1, input)?,
+                call(&plugin, plugin.part2, input)?,
+            ))
+        })),
+    }
+}
+
+/// Call a plugin part, taking ownership of the returned string
+fn call(plugin: &PluginDay, part: PluginPart, input: &str) -> SolveResult {
+    let mut out = ptr::null_mut();
+    // SAFETY: the input outlives the call and `out` is a valid place for the result
+    let status = unsafe { part(input.as_ptr(), input.len(), &mut out) };
+    let text = (!out.is_null()).then(|| {
+        // SAFETY: parts store NUL-terminated strings, released once by the plugin's `free`
+        unsafe {
+            let text = CStr::from_ptr(out).to_string_lossy().into_owned();
+            (plugin.free)(out);
+            text
+        }
+    });
+    match (status, text) {
+        (0, Some(answer)) => {
+            let Ok(answer) = answer.parse::<Solution>();
+            Ok(answer)
+        }
+        (0, None) => Err(SolveError::new("plugin returned no answer")),
+        (status, message) => {
+            Err(SolveError::new(message.unwrap_or_else(|| {
+                format!("plugin failed with status {}", status)
+            })))
+        }
+    }
+}
+
+/// Plugin side of a [`PluginPart`], used by [`export_plugin!`]: check the schema, run the
+/// solver catching panics, and store the answer or error in `out`
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `out` must be valid for writes.
+pub unsafe fn run_part(
+    schema: &Schema,
+    solver: fn(&str) -> SolveResult,
+    input: *const u8,
+    len: usize,
+    out: *mut *mut c_char,
+) -> i32 {
+    // SAFETY: guaranteed by the caller
+    let bytes = unsafe { std::slice::from_raw_parts(input, len) };
+    let result = match std::str::from_utf8(bytes) {
+        Ok(input) => match schema.validate(input) {
+            Ok(()) => error::catch_panic(|| solver(input)),
+            Err(violations) => Err(SolveError::from(&violations[0]).locate(input)),
+        },
+        Err(_) => Err(SolveError::new("input is not valid UTF-8")),
+    };
+    let (status, text) = match result {
+        Ok(answer) => (0, answer.to_string()),
+        Err(err) => (1, err.to_string()),
+    };
+    let text = CString::new(text.replace('\0', "")).expect("NUL bytes were removed");
+    // SAFETY: guaranteed by the caller
+    unsafe { *out = text.into_raw() };
+    status
+}
+
+/// Frees strings stored by [`run_part`]
+///
+/// # Safety
+/// `text` must be null or a string stored by `run_part` that wasn't freed yet.
+pub unsafe extern "C" fn free_string(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Export a [`Day`](crate::solution::Day) implementation as a plugin for `day`.
+/// Use once, in a crate built as a `cdylib`.
+#[macro_export]
+macro_rules! export_plugin {
+    ($day:expr, $solution:ty) => {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn aoc_plugin_register() -> *const $crate::plugin::PluginDay {
+            use $crate::solution::Day;
+
+            unsafe extern "C" fn part1(
+                input: *const u8,
+                len: usize,
+                out: *mut *mut ::std::ffi::c_char,
+            ) -> i32 {
+                let schema = &<$solution as Day>::SCHEMA;
+                unsafe { $crate::plugin::run_part(schema, <$solution>::part1, input, len, out) }
+            }
+
+            unsafe extern "C" fn part2(
+                input: *const u8,
+                len: usize,
+                out: *mut *mut ::std::ffi::c_char,
+            ) -> i32 {
+                let schema = &<$solution as Day>::SCHEMA;
+                unsafe { $crate::plugin::run_part(schema, <$solution>::part2, input, len, out) }
+            }
+
+            static DAY: $crate::plugin::PluginDay = $crate::plugin::PluginDay {
+                abi_version: $crate::plugin::ABI_VERSION,
+                day: $day,
+                part1,
+                part2,
+                free: $crate::plugin::free_string,
+            };
+            &DAY
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    // exercises both sides of the ABI in process
+    crate::export_plugin!(1, crate::day1::Solution);
+
+    fn plugin() -> DaySolvers {
+        // SAFETY: the registration is a static defined above
+        let plugin = unsafe { *aoc_plugin_register() };
+        assert_eq!(plugin.abi_version, ABI_VERSION);
+        solvers(1, plugin, "test")
+    }
+
+    #[test]
+    fn test_solve() {
+        let solvers = plugin();
+        assert_eq!((solvers.part1)(DAY1_INPUT).unwrap().to_string(), "3");
+        let (part1, part2) = (solvers.both)(DAY1_INPUT).unwrap();
+        assert_eq!(
+            (part1.to_string(), part2.to_string()),
+            ("3".into(), "6".into())
+        );
+    }
+
+    #[test]
+    fn test_typed_answers() {
+        unsafe extern "C" fn answer(_: *const u8, _: usize, out: *mut *mut c_char) -> i32 {
+            // SAFETY: `out` is valid for writes, see `PluginPart`
+            unsafe { *out = CString::new("42").unwrap().into_raw() };
+            0
+        }
+        let plugin = PluginDay {
+            abi_version: ABI_VERSION,
+            day: 1,
+            part1: answer,
+            part2: answer,
+            free: free_string,
+        };
+        // parsed like any other answer, so it compares with numbers from built-in days
+        let answer = call(&plugin, answer, "").unwrap();
+        assert_eq!(answer, Solution::U64(42));
+    }
+
+    #[test]
+    fn test_errors() {
+        let solvers = plugin();
+        // the schema is checked on the plugin side
+        let err = (solvers.part1)("L68\nX30").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("plugin-test-{}", std::process::id()));
+        assert!(load(&dir).is_empty());
+
+        fs::create_dir_all(&dir).unwrap();
+        let bogus = dir.join(format!("bogus.{}", std::env::consts::DLL_EXTENSION));
+        fs::write(&bogus, "not a library").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        let errors = load(&dir);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], PluginError::Load { path, .. } if *path == bogus));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
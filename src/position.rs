@@ -1,5 +1,3 @@
-#![allow(dead_code)]
-
 /// A 2D position with named fields.
 /// Uses (x, y) convention where x is column and y is row.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
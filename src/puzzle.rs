@@ -0,0 +1,511 @@
+//! Puzzle descriptions for reading in the terminal: the day's page is fetched once and cached
+//! next to the input, and its articles are converted to wrapped text that keeps emphasis,
+//! inline code and code blocks. The `<pre><code>` blocks, which hold the example inputs, can
+//! be extracted into example files.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::runner;
+
+/// Page of a day, `{day}` is filled in
+pub const DEFAULT_URL: &str = "https://adventofcode.com/2025/day/{day}";
+
+/// Width text is wrapped to
+pub const WIDTH: usize = 80;
+
+/// Indent of code blocks and list items
+const INDENT: &str = "    ";
+
+const BOLD: &str = "\x1b[1m";
+const CODE: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Where a day's page is cached, gitignored like the input
+pub fn cache_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("./input/day{}/puzzle.html", day))
+}
+
+pub fn url(template: &str, day: u8) -> String {
+    template.replace("{day}", &day.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    /// Tag name in lower case, and whether it closes
+    Tag(String, bool),
+    Text(&'a str),
+}
+
+/// Split HTML into tags and text. Comments and declarations are dropped, and so are
+/// scripts and styles with their content.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>') else {
+            tokens.push(Token::Text(&rest[start..]));
+            break;
+        };
+        let inside = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let closing = inside.starts_with('/');
+        let name: String = inside
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+        if !closing && (name == "script" || name == "style") {
+            let close = format!("</{}", name);
+            let lower = rest.to_ascii_lowercase();
+            rest = lower.find(&close).map_or("", |i| &rest[i..]);
+            continue;
+        }
+        tokens.push(Token::Tag(name, closing));
+    }
+    tokens
+}
+
+/// Replace character references
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let character = entity.and_then(|entity| match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let number = entity.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The part of the page worth reading: the puzzle articles, else `<main>`, else all of it
+fn content<'a>(tokens: &'a [Token<'a>]) -> Vec<&'a Token<'a>> {
+    let inside = |tag: &str| -> Vec<&Token> {
+        let mut depth = 0;
+        tokens
+            .iter()
+            .filter(|token| match token {
+                Token::Tag(name, closing) if name == tag => {
+                    depth = if *closing { depth - 1 } else { depth + 1 };
+                    false
+                }
+                _ => depth > 0,
+            })
+            .collect()
+    };
+    let articles = inside("article");
+    if !articles.is_empty() {
+        return articles;
+    }
+    let main = inside("main");
+    if !main.is_empty() {
+        return main;
+    }
+    tokens.iter().collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Heading,
+    Paragraph,
+    Item,
+    Code,
+}
+
+/// Convert a puzzle page to terminal text, with ANSI styles if `ansi`, otherwise with
+/// emphasis as `*text*` and inline code as `` `text` ``
+pub fn render(html: &str, ansi: bool) -> String {
+    let tokens = tokenize(html);
+    let mut blocks: Vec<(Kind, String)> = Vec::new();
+    let mut block: Option<(Kind, String)> = None;
+    // styles open in the current block, reapplied after one of them closes
+    let mut styles: Vec<&str> = Vec::new();
+
+    let mut finish = |block: &mut Option<(Kind, String)>| {
+        if let Some((kind, text)) = block.take() {
+            let text = match kind {
+                Kind::Code => text,
+                _ => text.trim().to_string(),
+            };
+            if !text.is_empty() {
+                blocks.push((kind, text));
+            }
+        }
+    };
+
+    for token in content(&tokens) {
+        match token {
+            Token::Text(text) => {
+                let in_code = matches!(block, Some((Kind::Code, _)));
+                let text = if in_code {
+                    decode(text)
+                } else {
+                    collapse(&decode(text))
+                };
+                if text.trim().is_empty() && block.is_none() {
+                    continue;
+                }
+                let (_, current) = block.get_or_insert((Kind::Paragraph, String::new()));
+                current.push_str(&text);
+            }
+            Token::Tag(name, closing) => {
+                let kind = match name.as_str() {
+                    "h1" | "h2" | "h3" => Some(Kind::Heading),
+                    "p" => Some(Kind::Paragraph),
+                    "li" => Some(Kind::Item),
+                    "pre" => Some(Kind::Code),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    finish(&mut block);
+                    styles.clear();
+                    if !closing {
+                        block = Some((kind, String::new()));
+                    }
+                    continue;
+                }
+
+                let in_code = matches!(block, Some((Kind::Code, _)));
+                let (style, marker) = match name.as_str() {
+                    "em" | "b" | "strong" => (BOLD, "*"),
+                    "code" if !in_code => (CODE, "`"),
+                    "br" => {
+                        if let Some((_, current)) = &mut block {
+                            current.push('\n');
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+                let (_, current) = block.get_or_insert((Kind::Paragraph, String::new()));
+                match (ansi, closing) {
+                    (true, false) => {
+                        styles.push(style);
+                        current.push_str(style);
+                    }
+                    (true, true) => {
+                        styles.pop();
+                        current.push_str(RESET);
+                        styles.iter().for_each(|style| current.push_str(style));
+                    }
+                    // markers would change the examples in code blocks
+                    (false, _) if !in_code => current.push_str(marker),
+                    (false, _) => {}
+                }
+            }
+        }
+    }
+    finish(&mut block);
+
+    let mut text = String::new();
+    for (kind, block) in blocks {
+        match kind {
+            Kind::Heading if ansi => text += &format!("{}{}{}\n\n", BOLD, block, RESET),
+            Kind::Heading => text += &format!("{}\n\n", block),
+            Kind::Paragraph => text += &(wrap(&block, "", "") + "\n"),
+            Kind::Item => text += &wrap(&block, "  - ", INDENT),
+            Kind::Code => {
+                for line in block.trim_end_matches('\n').lines() {
+                    text += &format!("{}{}\n", INDENT, line);
+                }
+                text.push('\n');
+            }
+        }
+    }
+    text.trim_end().to_string() + "\n"
+}
+
+/// Collapse runs of whitespace into one space
+fn collapse(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    for (i, word) in text.split_whitespace().enumerate() {
+        if i > 0 {
+            collapsed.push(' ');
+        }
+        collapsed.push_str(word);
+    }
+    if text.starts_with(char::is_whitespace) && !collapsed.is_empty() {
+        collapsed.insert(0, ' ');
+    }
+    if text.ends_with(char::is_whitespace) {
+        collapsed.push(' ');
+    }
+    collapsed
+}
+
+/// Width of text on the terminal, leaving out ANSI escapes
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut escape = false;
+    for c in text.chars() {
+        match (escape, c) {
+            (false, '\x1b') => escape = true,
+            (true, 'm') => escape = false,
+            (true, _) => {}
+            (false, _) => width += 1,
+        }
+    }
+    width
+}
+
+/// Wrap `text` to [`WIDTH`], starting with `first` and indenting further lines with `rest`
+fn wrap(text: &str, first: &str, rest: &str) -> String {
+    let mut wrapped = String::new();
+    for paragraph in text.split('\n') {
+        let mut line = first.to_string();
+        let mut empty = true;
+        for word in paragraph.split(' ').filter(|word| !word.is_empty()) {
+            if !empty && visible_width(&line) + 1 + visible_width(word) > WIDTH {
+                wrapped += line.trim_end();
+                wrapped.push('\n');
+                line = rest.to_string();
+                empty = true;
+            }
+            if !empty {
+                line.push(' ');
+            }
+            line.push_str(word);
+            empty = false;
+        }
+        wrapped += line.trim_end();
+        wrapped.push('\n');
+    }
+    wrapped
+}
+
+/// Text of every `<pre><code>` block, which hold the example inputs and sometimes diagrams
+pub fn examples(html: &str) -> Vec<String> {
+    let tokens = tokenize(html);
+    let mut examples = Vec::new();
+    let mut current: Option<String> = None;
+    let mut in_pre = false;
+    for token in content(&tokens) {
+        match token {
+            Token::Tag(name, closing) if name == "pre" => in_pre = !closing,
+            Token::Tag(name, false) if name == "code" && in_pre => current = Some(String::new()),
+            Token::Tag(name, true) if name == "code" => {
+                if let Some(example) = current.take().filter(|e| !e.trim().is_empty()) {
+                    let example = example.trim_end_matches('\n').to_string() + "\n";
+                    examples.push(example);
+                }
+            }
+            Token::Text(text) => {
+                if let Some(example) = &mut current {
+                    example.push_str(&decode(text));
+                }
+            }
+            Token::Tag(..) => {}
+        }
+    }
+    examples
+}
+
+/// Parse a choice of examples like `1,3` or `all` into indices below `count`
+pub fn select(choice: &str, count: usize) -> Result<Vec<usize>, String> {
+    let choice = choice.trim();
+    if choice.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+    let mut chosen = Vec::new();
+    for item in choice
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        match item.parse::<usize>() {
+            Ok(n @ 1..) if n <= count => chosen.push(n - 1),
+            _ => return Err(format!("no example {:?}, there are {}", item, count)),
+        }
+    }
+    chosen.sort();
+    chosen.dedup();
+    Ok(chosen)
+}
+
+/// Whether some example file of `day` already holds `example`
+pub fn is_saved(day: u8, example: &str) -> bool {
+    let dir = PathBuf::from(format!("./input/day{}", day));
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.filter_map(Result::ok).any(|entry| {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        name.starts_with("example")
+            && fs::read_to_string(entry.path()).is_ok_and(|saved| saved == example)
+    })
+}
+
+/// The examples as listed by `read`: numbered, with their line counts, whether they are
+/// saved, and the start of the first line
+pub fn list_examples(day: u8, examples: &[String]) -> String {
+    let mut text = String::from("\n=== Examples ===\n");
+    for (i, example) in examples.iter().enumerate() {
+        let first = example.lines().next().unwrap_or_default();
+        let saved = if is_saved(day, example) {
+            ", saved"
+        } else {
+            ""
+        };
+        text += &format!(
+            "  {}) {} lines{}: {}\n",
+            i + 1,
+            example.lines().count(),
+            saved,
+            first.chars().take(40).collect::<String>()
+        );
+    }
+    text
+}
+
+/// Save the `chosen` examples as new example files of `day`, skipping those already saved.
+/// Returns a line per example saying what was done.
+pub fn save_examples(day: u8, examples: &[String], chosen: &[usize]) -> io::Result<String> {
+    let mut text = String::new();
+    for &i in chosen {
+        if is_saved(day, &examples[i]) {
+            text += &format!("Example {} is already saved\n", i + 1);
+            continue;
+        }
+        let path = runner::new_example_path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &examples[i])?;
+        text += &format!("Wrote example {} to {}\n", i + 1, path.display());
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><head><title>Day 1</title><script>var x = "<p>no</p>";</script></head>
+<body><header>Navigation</header>
+<main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2>
+<p>The dial starts by pointing at <code>50</code>. You could follow the instructions, but
+your <em>recent</em> required official North Pole secret entrance security training
+seminar taught you that the safe is actually a decoy.</p>
+<p>For example, suppose the attached document contained the following rotations:</p>
+<pre><code>L68
+L30
+<em>R48</em>
+</code></pre>
+<ul><li>The dial starts at <code>50</code>.</li><li>It ends at <code>0</code> &amp; stops.</li></ul>
+</article>
+<p>Answer: <input type="text"/></p>
+</main></body></html>"#;
+
+    #[test]
+    fn test_render_plain() {
+        let text = render(PAGE, false);
+        let expected = "\
+--- Day 1: Secret Entrance ---
+
+The dial starts by pointing at `50`. You could follow the instructions, but your
+*recent* required official North Pole secret entrance security training seminar
+taught you that the safe is actually a decoy.
+
+For example, suppose the attached document contained the following rotations:
+
+    L68
+    L30
+    R48
+
+  - The dial starts at `50`.
+  - It ends at `0` & stops.
+";
+        assert_eq!(text, expected);
+        assert!(text.lines().all(|line| line.chars().count() <= WIDTH));
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let text = render(PAGE, true);
+        assert!(text.starts_with("\x1b[1m--- Day 1"));
+        assert!(text.contains("your\n\x1b[1mrecent\x1b[0m required"));
+        assert!(text.contains("    \x1b[1mR48\x1b[0m\n"));
+        assert!(!text.contains("Navigation") && !text.contains("no</p>"));
+
+        // styles nest
+        let nested = render("<p><code>a<em>b</em>c</code></p>", true);
+        assert_eq!(nested, "\x1b[36ma\x1b[1mb\x1b[0m\x1b[36mc\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(examples(PAGE), ["L68\nL30\nR48\n"]);
+        assert_eq!(
+            decode("&lt;a&gt; &#65;&#x42; & &bogus;"),
+            "<a> AB & &bogus;"
+        );
+    }
+
+    #[test]
+    fn test_list_examples() {
+        let examples = examples(PAGE);
+        // no day 26, so nothing is saved
+        assert_eq!(
+            list_examples(26, &examples),
+            "\n=== Examples ===\n  1) 3 lines: L68\n"
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select("all", 3), Ok(vec![0, 1, 2]));
+        assert_eq!(select(" 3, 1,1 ", 3), Ok(vec![0, 2]));
+        assert_eq!(select("", 3), Ok(vec![]));
+        assert!(select("4", 3).is_err());
+        assert!(select("0", 3).is_err());
+    }
+}
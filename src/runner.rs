@@ -0,0 +1,222 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answers::Answers;
+use crate::crypt::{self, CryptError};
+use crate::error::{self, SolveError};
+use crate::get_day_solvers;
+use crate::solution::Solution;
+
+/// Where the runner looks for a day's puzzle input
+pub fn input_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("./input/day{}/input.txt", day))
+}
+
+/// First free example file of a day: `input/dayN/example.txt`, then `example2.txt`, ...
+pub fn new_example_path(day: u8) -> PathBuf {
+    (1..)
+        .map(|n| match n {
+            1 => PathBuf::from(format!("./input/day{}/example.txt", day)),
+            n => PathBuf::from(format!("./input/day{}/example{}.txt", day, n)),
+        })
+        .find(|path| !path.exists())
+        .expect("some example number is free")
+}
+
+/// Read a day's puzzle input, decrypting the encrypted copy if there is no plaintext.
+/// Returns None if the day has no input.
+pub fn read_input(day: u8) -> Result<Option<String>, CryptError> {
+    crypt::read_text(&input_path(day))
+}
+
+/// What happened when running a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Outcome {
+    /// There is no input file for the day
+    Skipped,
+    /// The input doesn't match the day's schema, one located error per violation
+    InvalidInput(Vec<SolveError>),
+    /// The solver returned an error or panicked, located against the input
+    Failed(SolveError),
+    /// The requested parts were solved
+    Solved {
+        part1: Option<Solution>,
+        part2: Option<Solution>,
+    },
+}
+
+/// Result of running a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayResult {
+    pub day: u8,
+    pub outcome: Outcome,
+    /// Time spent in the solver, zero if it never ran
+    pub elapsed: Duration,
+    /// Whether the answers and timing were taken from the result cache
+    pub cached: bool,
+}
+
+impl DayResult {
+    /// Answer for a part, if it was solved
+    pub fn part(&self, part: u8) -> Option<&Solution> {
+        match &self.outcome {
+            Outcome::Solved { part1, .. } if part == 1 => part1.as_ref(),
+            Outcome::Solved { part2, .. } if part == 2 => part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Answer for a part, or why there is none
+    pub fn answer(&self, part: u8) -> Result<&Solution, String> {
+        match (&self.outcome, self.part(part)) {
+            (_, Some(sol)) => Ok(sol),
+            (Outcome::Skipped, _) => Err("no input".into()),
+            (Outcome::InvalidInput(_), _) => Err("invalid input".into()),
+            (Outcome::Failed(err), _) => Err(err.message().to_string()),
+            (Outcome::Solved { .. }, None) => Err("part was not solved".into()),
+        }
+    }
+
+    /// Solved parts with their answers
+    pub fn answers(&self) -> impl Iterator<Item = (u8, &Solution)> {
+        [1, 2]
+            .into_iter()
+            .filter_map(|part| self.part(part).map(|sol| (part, sol)))
+    }
+
+    pub fn elapsed_ms(&self) -> f64 {
+        self.elapsed.as_nanos() as f64 / 1_000_000.0
+    }
+
+    /// Whether the day ran cleanly and every answer matches the expected ones, if any.
+    /// Skipped days count as passed.
+    pub fn passed(&self, expected: Option<&Answers>) -> bool {
+        match self.outcome {
+            Outcome::Skipped => true,
+            Outcome::InvalidInput(_) | Outcome::Failed(_) => false,
+            Outcome::Solved { .. } => self
+                .answers()
+                .all(|(part, sol)| expected.is_none_or(|exp| exp.matches(part, sol))),
+        }
+    }
+}
+
+/// Run a day on its puzzle input from `input/dayN/input.txt`, or its encrypted copy.
+/// `part` selects a single part, otherwise both are solved together.
+pub fn run_day(day: u8, part: Option<u8>) -> DayResult {
+    let outcome = match read_input(day) {
+        Ok(Some(input)) => return solve(day, &input, part),
+        Ok(None) => Outcome::Skipped,
+        Err(err) => Outcome::Failed(SolveError::new(format!("cannot read input: {}", err))),
+    };
+    DayResult {
+        day,
+        outcome,
+        elapsed: Duration::ZERO,
+        cached: false,
+    }
+}
+
+/// Run a day on the given input. The input is checked against the day's schema first, and
+/// solver errors and panics are reported in the outcome rather than propagated.
+pub fn solve(day: u8, input: &str, part: Option<u8>) -> DayResult {
+    let result = |outcome, elapsed| DayResult {
+        day,
+        outcome,
+        elapsed,
+        cached: false,
+    };
+
+    let Some(solvers) = get_day_solvers(day) else {
+        let err = SolveError::new(format!("day {} is not implemented", day));
+        return result(Outcome::Failed(err), Duration::ZERO);
+    };
+
+    if let Err(violations) = solvers.schema.validate(input) {
+        let errors = violations
+            .iter()
+            .map(|violation| SolveError::from(violation).locate(input))
+            .collect();
+        return result(Outcome::InvalidInput(errors), Duration::ZERO);
+    }
+
+    let start = Instant::now();
+
+    let solved = error::catch_panic(|| match part {
+        Some(1) => (solvers.part1)(input).map(|a| (Some(a), None)),
+        Some(2) => (solvers.part2)(input).map(|b| (None, Some(b))),
+        _ => (solvers.both)(input).map(|(a, b)| (Some(a), Some(b))),
+    });
+
+    let elapsed = start.elapsed();
+
+    let outcome = match solved {
+        Ok((part1, part2)) => Outcome::Solved { part1, part2 },
+        Err(err) => Outcome::Failed(err.locate(input)),
+    };
+    result(outcome, elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn test_solve() {
+        let result = solve(1, DAY1_INPUT, None);
+        assert_eq!(result.day, 1);
+        let answers: Vec<_> = result
+            .answers()
+            .map(|(part, sol)| (part, sol.clone()))
+            .collect();
+        assert_eq!(
+            answers,
+            [(1, Solution::from(3usize)), (2, Solution::from(6usize))]
+        );
+        assert!(result.passed(None));
+    }
+
+    #[test]
+    fn test_solve_single_part() {
+        let result = solve(1, DAY1_INPUT, Some(2));
+        assert!(result.part(1).is_none());
+        assert_eq!(result.part(2), Some(&Solution::from(6usize)));
+        assert_eq!(result.answer(1), Err("part was not solved".into()));
+    }
+
+    #[test]
+    fn test_verify() {
+        let result = solve(1, DAY1_INPUT, None);
+        let right = Answers {
+            part1: "3".to_string(),
+            part2: "6".to_string(),
+        };
+        let wrong = Answers {
+            part1: "3".to_string(),
+            part2: "7".to_string(),
+        };
+        assert!(result.passed(Some(&right)));
+        assert!(!result.passed(Some(&wrong)));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let result = solve(1, "L68\nX30", None);
+        let Outcome::InvalidInput(errors) = &result.outcome else {
+            panic!("expected invalid input, got {:?}", result.outcome);
+        };
+        assert_eq!(errors[0].location().map(|loc| loc.line), Some(2));
+        assert!(!result.passed(None));
+        assert_eq!(result.answer(1), Err("invalid input".into()));
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        let result = solve(0, "", None);
+        assert!(matches!(result.outcome, Outcome::Failed(_)));
+    }
+}
@@ -0,0 +1,332 @@
+//! Solving days in a forked child process under address-space and CPU-time limits, so a
+//! runaway solver is reported as a failed day instead of taking the runner down with it.
+//! The child sends its result back over a pipe.
+//!
+//! Only the thread calling [`solve`] survives the fork, so a lock held by any other thread,
+//! like one of rayon's global pool, would never be released in the child. Sandboxing is
+//! therefore refused once the process runs other threads (see [`check`]): it doesn't combine
+//! with paths that start them, like `--determinism` or `--oracle`, and the runner never
+//! starts rayon's pool itself, as solving only happens in the children.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::fd::FromRawFd;
+use std::time::{Duration, Instant};
+
+use crate::error::SolveError;
+use crate::runner::{self, DayResult, Outcome};
+
+/// Exit code of a child whose allocation failed under the address-space limit
+const OUT_OF_MEMORY_EXIT: i32 = 86;
+/// Exit code of a child that couldn't apply its limits or send its result
+const CHILD_ERROR_EXIT: i32 = 87;
+
+/// Limits applied to the child, None leaves the inherited limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Address space in bytes
+    pub memory: Option<u64>,
+    /// CPU time, rounded up to whole seconds
+    pub cpu: Option<Duration>,
+}
+
+/// Solve a day like [`runner::solve`], in a child process under `limits`. Fails the day if
+/// the process isn't single-threaded, see [`check`].
+pub fn solve(day: u8, input: &str, part: Option<u8>, limits: Limits) -> DayResult {
+    if let Err(message) = check() {
+        return DayResult {
+            day,
+            outcome: Outcome::Failed(SolveError::new(message)),
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+    }
+    in_child(day, limits, || runner::solve(day, input, part))
+}
+
+/// Whether the process can be forked safely: it must run no thread but the calling one.
+/// Where the threads can't be counted, only Linux has `/proc/self/task`, this trusts the
+/// caller.
+pub fn check() -> Result<(), String> {
+    match thread_count() {
+        Some(threads) if threads > 1 => Err(format!(
+            "cannot sandbox: the process runs {} threads, which a forked child could deadlock on",
+            threads
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn thread_count() -> Option<usize> {
+    std::fs::read_dir("/proc/self/task")
+        .ok()
+        .map(|tasks| tasks.count())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn thread_count() -> Option<usize> {
+    None
+}
+
+/// Run `run` in a forked child under `limits` and return its result. Hitting a limit or
+/// crashing makes the day fail.
+fn in_child(day: u8, limits: Limits, run: impl FnOnce() -> DayResult) -> DayResult {
+    let failed = |message: String, elapsed| DayResult {
+        day,
+        outcome: Outcome::Failed(SolveError::new(message)),
+        elapsed,
+        cached: false,
+    };
+
+    // buffered output would be written by both processes
+    let _ = io::stdout().flush();
+    let mut fds = [0; 2];
+    // SAFETY: fds has room for the two descriptors
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        let err = io::Error::last_os_error();
+        return failed(format!("cannot create pipe: {}", err), Duration::ZERO);
+    }
+    let [read_fd, write_fd] = fds;
+
+    let start = Instant::now();
+    // SAFETY: the child only solves and exits, see the module docs
+    match unsafe { libc::fork() } {
+        -1 => {
+            let err = io::Error::last_os_error();
+            // SAFETY: both ends are open and owned here
+            unsafe {
+                libc::close(read_fd);
+                libc::close(write_fd);
+            }
+            failed(format!("cannot fork: {}", err), Duration::ZERO)
+        }
+        0 => {
+            // SAFETY: the read end belongs to the parent, the write end to the child
+            unsafe { libc::close(read_fd) };
+            let mut pipe = unsafe { File::from_raw_fd(write_fd) };
+            let code = match child(limits, run, &mut pipe) {
+                Ok(()) => 0,
+                Err(_) => CHILD_ERROR_EXIT,
+            };
+            // SAFETY: leaves without running the parent's destructors and exit handlers
+            unsafe { libc::_exit(code) }
+        }
+        pid => {
+            // SAFETY: as above, with the ends swapped
+            unsafe { libc::close(write_fd) };
+            let mut pipe = unsafe { File::from_raw_fd(read_fd) };
+            let mut bytes = Vec::new();
+            let read = pipe.read_to_end(&mut bytes);
+            let status = wait(pid);
+            let elapsed = start.elapsed();
+
+            match status {
+                Ok(status) => match exit(status, limits) {
+                    Exit::Clean if read.is_ok() => {
+                        serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+                            failed(format!("invalid result from child: {}", err), elapsed)
+                        })
+                    }
+                    Exit::Clean => failed("cannot read result from child".into(), elapsed),
+                    Exit::Failed(message) => failed(message, elapsed),
+                },
+                Err(err) => failed(format!("cannot wait for child: {}", err), elapsed),
+            }
+        }
+    }
+}
+
+/// The child's side: apply the limits, solve and send the result
+fn child(limits: Limits, run: impl FnOnce() -> DayResult, pipe: &mut File) -> io::Result<()> {
+    if let Some(memory) = limits.memory {
+        set_limit(libc::RLIMIT_AS, memory, memory)?;
+    }
+    if let Some(cpu) = limits.cpu {
+        // SIGXCPU at the soft limit, SIGKILL at the hard one if SIGXCPU is ignored
+        let secs = cpu.as_secs() + u64::from(cpu.subsec_nanos() > 0);
+        set_limit(libc::RLIMIT_CPU, secs.max(1), secs.max(1) + 1)?;
+    }
+    std::alloc::set_alloc_error_hook(|_| {
+        // SAFETY: allocating to report it could fail again, so leave at once
+        unsafe { libc::_exit(OUT_OF_MEMORY_EXIT) }
+    });
+
+    let result = run();
+    let bytes = serde_json::to_vec(&result).map_err(io::Error::other)?;
+    pipe.write_all(&bytes)
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+type Resource = libc::c_int;
+
+fn set_limit(resource: Resource, soft: u64, hard: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    };
+    // SAFETY: limit is a valid rlimit
+    match unsafe { libc::setrlimit(resource, &limit) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// Wait for the child to exit, returning its wait status
+fn wait(pid: libc::pid_t) -> io::Result<i32> {
+    let mut status = 0;
+    loop {
+        // SAFETY: status is valid for writes
+        if unsafe { libc::waitpid(pid, &mut status, 0) } == pid {
+            return Ok(status);
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// How the child ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Exit {
+    /// It sent its result
+    Clean,
+    Failed(String),
+}
+
+/// Interpret a wait status, blaming the limits for the signals they cause
+fn exit(status: i32, limits: Limits) -> Exit {
+    let memory_limit = || match limits.memory {
+        Some(memory) => format!(
+            "RESOURCE LIMIT: memory limit of {} MiB exceeded",
+            memory >> 20
+        ),
+        None => "RESOURCE LIMIT: out of memory".into(),
+    };
+
+    if libc::WIFEXITED(status) {
+        return match libc::WEXITSTATUS(status) {
+            0 => Exit::Clean,
+            OUT_OF_MEMORY_EXIT => Exit::Failed(memory_limit()),
+            CHILD_ERROR_EXIT => {
+                Exit::Failed("child process could not apply its limits or send its result".into())
+            }
+            code => Exit::Failed(format!("child process exited with code {}", code)),
+        };
+    }
+    let signal = libc::WTERMSIG(status);
+    match (signal, limits) {
+        (libc::SIGXCPU | libc::SIGKILL, Limits { cpu: Some(cpu), .. }) => Exit::Failed(format!(
+            "RESOURCE LIMIT: CPU time limit of {:?} exceeded",
+            cpu
+        )),
+        // an allocation outside the hook's reach, like a thread's stack
+        (
+            libc::SIGABRT,
+            Limits {
+                memory: Some(_), ..
+            },
+        ) => Exit::Failed(memory_limit()),
+        _ => Exit::Failed(format!("child process killed by signal {}", signal)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+    const LIMITS: Limits = Limits {
+        memory: Some(4 << 30),
+        cpu: Some(Duration::from_secs(10)),
+    };
+
+    fn failure(result: &DayResult) -> String {
+        match &result.outcome {
+            Outcome::Failed(err) => err.message().to_string(),
+            other => panic!("expected a failure, got {:?}", other),
+        }
+    }
+
+    /// Like [`solve`] without the check, which the test harness's threads would fail.
+    /// The children only solve, without taking locks the other threads could hold.
+    fn solve_unchecked(day: u8, input: &str, limits: Limits) -> DayResult {
+        in_child(day, limits, || runner::solve(day, input, None))
+    }
+
+    #[test]
+    fn test_solve() {
+        let result = solve_unchecked(1, DAY1_INPUT, LIMITS);
+        assert_eq!(
+            result.part(1).map(|sol| sol.to_string()).as_deref(),
+            Some("3")
+        );
+        assert_eq!(
+            result.part(2).map(|sol| sol.to_string()).as_deref(),
+            Some("6")
+        );
+        assert!(!result.elapsed.is_zero());
+
+        let invalid = solve_unchecked(1, "L68\nX30", LIMITS);
+        let Outcome::InvalidInput(errors) = invalid.outcome else {
+            panic!("invalid input was solved");
+        };
+        assert_eq!(errors[0].location().map(|loc| loc.line), Some(2));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_threads() {
+        let (done, wait) = std::sync::mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || wait.recv());
+        assert!(thread_count().unwrap() > 1);
+        let result = solve(1, DAY1_INPUT, None, LIMITS);
+        assert!(
+            failure(&result).starts_with("cannot sandbox"),
+            "{:?}",
+            result
+        );
+        drop(done);
+        thread.join().unwrap().unwrap_err();
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let limits = Limits {
+            memory: Some(512 << 20),
+            cpu: None,
+        };
+        let result = in_child(1, limits, || {
+            let huge = vec![1u8; 8 << 30];
+            std::hint::black_box(huge);
+            unreachable!("the allocation should fail")
+        });
+        assert_eq!(
+            failure(&result),
+            "RESOURCE LIMIT: memory limit of 512 MiB exceeded"
+        );
+    }
+
+    #[test]
+    fn test_cpu_limit() {
+        let limits = Limits {
+            memory: None,
+            cpu: Some(Duration::from_millis(200)),
+        };
+        let result = in_child(1, limits, || {
+            loop {
+                std::hint::black_box(0);
+            }
+        });
+        assert!(failure(&result).starts_with("RESOURCE LIMIT: CPU time limit"));
+    }
+
+    #[test]
+    fn test_crash() {
+        let result = in_child(1, LIMITS, || unsafe { libc::_exit(3) });
+        assert_eq!(failure(&result), "child process exited with code 3");
+    }
+}
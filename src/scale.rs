@@ -0,0 +1,237 @@
+//! Empirical complexity: a day is timed on generated inputs of increasing scale and a power
+//! law `time = c * scale^k` is fitted to the times, by least squares on their logarithms.
+
+use std::time::Duration;
+
+use crate::budget::{self, Timing};
+use crate::generate;
+
+/// `count` scales halving from `max`, largest first
+pub fn scales(max: f64, count: usize) -> Vec<f64> {
+    (0..count).map(|i| max / 2f64.powi(i as i32)).collect()
+}
+
+/// A day timed at one scale
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub scale: f64,
+    /// Size of the generated input
+    pub lines: usize,
+    pub bytes: usize,
+    pub timing: Timing,
+}
+
+/// Time `day` on generated inputs at each of `scales`. None if the day has no generator.
+pub fn measure(day: u8, scales: &[f64], seed: u64) -> Option<Vec<Measurement>> {
+    let mut measurements = Vec::with_capacity(scales.len());
+    for &scale in scales {
+        let input = generate::generate(day, scale, seed)?;
+        measurements.push(Measurement {
+            scale,
+            lines: input.lines().count(),
+            bytes: input.len(),
+            timing: budget::benchmark(day, &input),
+        });
+    }
+    Some(measurements)
+}
+
+/// Fitted power law `time = coefficient * scale^exponent`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    pub exponent: f64,
+    /// Time at scale 1, in seconds
+    pub coefficient: f64,
+    /// Share of the variance of the log times explained by the fit, 1 is a perfect fit
+    pub r_squared: f64,
+}
+
+impl Fit {
+    /// Fit `(scale, seconds)` points. None with fewer than two distinct scales or a
+    /// non-positive value, which has no logarithm.
+    pub fn new(points: &[(f64, f64)]) -> Option<Self> {
+        if points
+            .iter()
+            .any(|&(scale, secs)| scale <= 0.0 || secs <= 0.0)
+        {
+            return None;
+        }
+        let logs: Vec<(f64, f64)> = points.iter().map(|&(s, t)| (s.ln(), t.ln())).collect();
+        let n = logs.len() as f64;
+        let mean_x = logs.iter().map(|&(x, _)| x).sum::<f64>() / n;
+        let mean_y = logs.iter().map(|&(_, y)| y).sum::<f64>() / n;
+        let sxx: f64 = logs.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = logs.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let syy: f64 = logs.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+        if sxx < f64::EPSILON {
+            return None;
+        }
+
+        let exponent = sxy / sxx;
+        let intercept = mean_y - exponent * mean_x;
+        let r_squared = if syy < f64::EPSILON {
+            1.0
+        } else {
+            (sxy * sxy / (sxx * syy)).clamp(0.0, 1.0)
+        };
+        Some(Self {
+            exponent,
+            coefficient: intercept.exp(),
+            r_squared,
+        })
+    }
+
+    /// Fit the measured points, leaving out failed runs
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let points: Vec<(f64, f64)> = measurements
+            .iter()
+            .filter_map(|m| match m.timing {
+                Timing::Measured(elapsed) => Some((m.scale, elapsed.as_secs_f64())),
+                Timing::Failed(_) => None,
+            })
+            .collect();
+        Self::new(&points)
+    }
+
+    /// Projected time at `scale`
+    pub fn predict(&self, scale: f64) -> Duration {
+        Duration::from_secs_f64(self.coefficient * scale.powf(self.exponent))
+    }
+}
+
+/// A day timed at several scales, as run by `scale`
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub seed: u64,
+    /// Smallest scale first
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    /// Time `day` at each of `scales`, smallest first. None if the day has no generator.
+    pub fn run(day: u8, scales: &[f64], seed: u64) -> Option<Self> {
+        let mut scales = scales.to_vec();
+        scales.sort_by(f64::total_cmp);
+        Some(Self {
+            day,
+            seed,
+            measurements: measure(day, &scales, seed)?,
+        })
+    }
+
+    /// Power law fitted to the successful runs, None if there are too few
+    pub fn fit(&self) -> Option<Fit> {
+        Fit::from_measurements(&self.measurements)
+    }
+
+    /// The report as printed by `scale`: the size and time at each scale, then the fitted
+    /// complexity and the time it projects for a 10x input, if there is a fit
+    pub fn render(&self) -> String {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let mut text = format!(
+            "=== Day {:02} scaling (seed {}) ===\n\n",
+            self.day, self.seed
+        );
+        text += "    Scale     Lines       Bytes        Time\n";
+        for m in &self.measurements {
+            let time = match &m.timing {
+                Timing::Measured(elapsed) => format!("{:.4} ms", ms(*elapsed)),
+                Timing::Failed(result) => match result.answer(1) {
+                    Err(message) => format!("FAILED  {}", message.lines().next().unwrap_or("")),
+                    Ok(_) => "FAILED".to_string(),
+                },
+            };
+            text += &format!(
+                "  {:>7}  {:>8}  {:>10}  {:>13}\n",
+                format!("{}x", m.scale),
+                m.lines,
+                m.bytes,
+                time
+            );
+        }
+
+        if let Some(fit) = self.fit() {
+            text += &format!(
+                "\n  Estimated complexity: O(n^{:.2}) (R² {:.3})\n",
+                fit.exponent, fit.r_squared
+            );
+            text += &format!(
+                "  Projected time at 10x input: {:.4} ms\n",
+                ms(fit.predict(10.0))
+            );
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scales() {
+        assert_eq!(scales(4.0, 4), [4.0, 2.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_fit() {
+        let quadratic: Vec<(f64, f64)> = [0.5, 1.0, 2.0, 4.0]
+            .iter()
+            .map(|&s| (s, 0.003 * s * s))
+            .collect();
+        let fit = Fit::new(&quadratic).unwrap();
+        assert!((fit.exponent - 2.0).abs() < 1e-9);
+        assert!((fit.coefficient - 0.003).abs() < 1e-9);
+        assert!((fit.r_squared - 1.0).abs() < 1e-9);
+        assert!((fit.predict(10.0).as_secs_f64() - 0.3).abs() < 1e-6);
+
+        let flat = Fit::new(&[(1.0, 0.5), (2.0, 0.5)]).unwrap();
+        assert_eq!(flat.exponent, 0.0);
+        assert_eq!(Fit::new(&[(1.0, 0.5), (1.0, 0.7)]), None);
+        assert_eq!(Fit::new(&[(1.0, 0.0), (2.0, 0.7)]), None);
+    }
+
+    #[test]
+    fn test_measure() {
+        let measurements = measure(1, &[0.02, 0.01], generate::DEFAULT_SEED).unwrap();
+        assert_eq!(measurements[0].lines, 80);
+        assert_eq!(measurements[1].lines, 40);
+        assert!(
+            measurements
+                .iter()
+                .all(|m| matches!(m.timing, Timing::Measured(_)))
+        );
+        assert!(measure(25, &[1.0], generate::DEFAULT_SEED).is_none());
+    }
+
+    #[test]
+    fn test_render() {
+        let measurement = |scale: f64, ms: u64| Measurement {
+            scale,
+            lines: (scale * 100.0) as usize,
+            bytes: (scale * 400.0) as usize,
+            timing: Timing::Measured(Duration::from_millis(ms)),
+        };
+        let report = Report {
+            day: 1,
+            seed: 7,
+            measurements: vec![measurement(1.0, 2), measurement(2.0, 8)],
+        };
+        let expected = "\
+=== Day 01 scaling (seed 7) ===
+
+    Scale     Lines       Bytes        Time
+       1x       100         400      2.0000 ms
+       2x       200         800      8.0000 ms
+
+  Estimated complexity: O(n^2.00) (R² 1.000)
+  Projected time at 10x input: 200.0000 ms
+";
+        assert_eq!(report.render(), expected);
+
+        let report = Report::run(1, &[0.02, 0.01], generate::DEFAULT_SEED).unwrap();
+        assert_eq!(report.measurements[0].scale, 0.01);
+        assert!(Report::run(25, &[1.0], generate::DEFAULT_SEED).is_none());
+    }
+}
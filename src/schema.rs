@@ -0,0 +1,368 @@
+use std::fmt::{Display, Formatter};
+
+/// A single element of a line pattern.
+#[derive(Debug, Clone, Copy)]
+pub enum Token {
+    /// Exact text
+    Lit(&'static str),
+    /// One or more ASCII digits
+    Uint,
+    /// Optional leading '-' followed by one or more ASCII digits
+    Int,
+    /// Exactly one character out of the given set
+    OneOf(&'static str),
+    /// One or more spaces
+    Ws,
+    /// Zero or more spaces
+    OptWs,
+    /// One or more `item`s separated by `sep`
+    List {
+        item: &'static [Token],
+        sep: &'static [Token],
+    },
+}
+
+/// Declarative description of the expected shape of a day's input.
+/// Checked by the runner before the solver is called.
+#[derive(Debug, Clone, Copy)]
+pub enum Schema {
+    /// No constraints
+    Any,
+    /// Every line matches the pattern
+    Lines(&'static [Token]),
+    /// Every line but the last matches `body`, the last line matches `last`
+    Trailer {
+        body: &'static [Token],
+        last: &'static [Token],
+    },
+    /// Rectangular grid where every cell is one of the allowed characters
+    Grid(&'static str),
+    /// Blank-line separated sections, each checked against its own schema
+    Sections(&'static [Schema]),
+}
+
+/// A single place where the input does not match the schema.
+/// Line and column are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Violation {
+    fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Schema {
+    /// Check the input against the schema, returning every violation found.
+    pub fn validate(&self, input: &str) -> Result<(), Vec<Violation>> {
+        let lines: Vec<(usize, &str)> =
+            input.lines().enumerate().map(|(i, l)| (i + 1, l)).collect();
+        let mut violations = Vec::new();
+        self.check(&lines, &mut violations);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    fn check(&self, lines: &[(usize, &str)], out: &mut Vec<Violation>) {
+        match self {
+            Schema::Any => {}
+            Schema::Lines(pattern) => {
+                if lines.is_empty() {
+                    out.push(Violation::new(1, 1, "expected at least one line"));
+                }
+                for &(line_no, line) in lines {
+                    check_line(pattern, line_no, line, out);
+                }
+            }
+            Schema::Trailer { body, last } => match lines.split_last() {
+                Some((&(line_no, line), rest)) => {
+                    for &(line_no, line) in rest {
+                        check_line(body, line_no, line, out);
+                    }
+                    check_line(last, line_no, line, out);
+                }
+                None => out.push(Violation::new(1, 1, "expected at least one line")),
+            },
+            Schema::Grid(alphabet) => check_grid(alphabet, lines, out),
+            Schema::Sections(schemas) => check_sections(schemas, lines, out),
+        }
+    }
+}
+
+fn check_grid(alphabet: &str, lines: &[(usize, &str)], out: &mut Vec<Violation>) {
+    let Some(&(_, first)) = lines.first() else {
+        out.push(Violation::new(1, 1, "expected a grid, found no lines"));
+        return;
+    };
+    let width = first.chars().count();
+
+    for &(line_no, line) in lines {
+        let mut line_width = 0;
+        for (col, c) in line.chars().enumerate() {
+            line_width += 1;
+            if !alphabet.contains(c) {
+                out.push(Violation::new(
+                    line_no,
+                    col + 1,
+                    format!("unexpected character {c:?}, expected one of {alphabet:?}"),
+                ));
+            }
+        }
+
+        if line_width != width {
+            out.push(Violation::new(
+                line_no,
+                line_width.min(width) + 1,
+                format!("ragged grid row, expected width {width}, found {line_width}"),
+            ));
+        }
+    }
+}
+
+fn check_sections(schemas: &[Schema], lines: &[(usize, &str)], out: &mut Vec<Violation>) {
+    let mut sections: Vec<&[(usize, &str)]> = Vec::new();
+    let mut start = 0;
+    for (idx, &(line_no, line)) in lines.iter().enumerate() {
+        if line.is_empty() {
+            if idx == start {
+                // either the input starts with a blank line or two blank lines follow each other
+                out.push(Violation::new(line_no, 1, "unexpected blank line"));
+            } else {
+                sections.push(&lines[start..idx]);
+            }
+            start = idx + 1;
+        }
+    }
+    if start < lines.len() {
+        sections.push(&lines[start..]);
+    }
+
+    if sections.len() != schemas.len() {
+        let line_no = lines.last().map_or(1, |&(line_no, _)| line_no + 1);
+        out.push(Violation::new(
+            line_no,
+            1,
+            format!(
+                "expected {} blank-line separated sections, found {}",
+                schemas.len(),
+                sections.len()
+            ),
+        ));
+    }
+
+    for (schema, section) in schemas.iter().zip(sections) {
+        schema.check(section, out);
+    }
+}
+
+fn check_line(pattern: &[Token], line_no: usize, line: &str, out: &mut Vec<Violation>) {
+    let chars: Vec<char> = line.chars().collect();
+    let mut matcher = Matcher {
+        chars: &chars,
+        furthest: 0,
+        expected: Vec::new(),
+    };
+
+    let matched = matcher
+        .seq(pattern, 0)
+        .filter(|&end| end == chars.len() || matcher.fail(end, "end of line"));
+
+    if matched.is_none() {
+        let found = match chars.get(matcher.furthest) {
+            Some(c) => format!("{c:?}"),
+            None if chars.is_empty() => "empty line".to_string(),
+            None => "end of line".to_string(),
+        };
+        out.push(Violation::new(
+            line_no,
+            matcher.furthest + 1,
+            format!("expected {}, found {found}", matcher.expected.join(" or ")),
+        ));
+    }
+}
+
+/// Greedy pattern matcher that remembers the furthest position it failed at,
+/// so errors point at the most relevant column.
+struct Matcher<'a> {
+    chars: &'a [char],
+    furthest: usize,
+    expected: Vec<String>,
+}
+
+impl Matcher<'_> {
+    /// Record a failure at `pos`, always returns false
+    fn fail(&mut self, pos: usize, expected: impl Into<String>) -> bool {
+        let expected = expected.into();
+        if pos > self.furthest {
+            self.furthest = pos;
+            self.expected = vec![expected];
+        } else if pos == self.furthest && !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+        false
+    }
+
+    fn seq(&mut self, tokens: &[Token], mut pos: usize) -> Option<usize> {
+        for token in tokens {
+            pos = self.token(token, pos)?;
+        }
+        Some(pos)
+    }
+
+    /// Count how many characters from `pos` satisfy the predicate
+    fn run(&self, pos: usize, pred: impl Fn(char) -> bool) -> usize {
+        self.chars[pos..].iter().take_while(|&&c| pred(c)).count()
+    }
+
+    fn token(&mut self, token: &Token, pos: usize) -> Option<usize> {
+        match *token {
+            Token::Lit(text) => {
+                let len = text.chars().count();
+                let matches = self.chars.len() >= pos + len
+                    && self.chars[pos..pos + len].iter().copied().eq(text.chars());
+                (matches || self.fail(pos, format!("{text:?}"))).then_some(pos + len)
+            }
+            Token::Uint => {
+                let len = self.run(pos, |c| c.is_ascii_digit());
+                (len > 0 || self.fail(pos, "digit")).then_some(pos + len)
+            }
+            Token::Int => {
+                let sign = usize::from(self.chars.get(pos) == Some(&'-'));
+                let len = self.run(pos + sign, |c| c.is_ascii_digit());
+                (len > 0 || self.fail(pos + sign, "digit")).then_some(pos + sign + len)
+            }
+            Token::OneOf(set) => {
+                let matches = self.chars.get(pos).is_some_and(|&c| set.contains(c));
+                (matches || self.fail(pos, format!("one of {set:?}"))).then_some(pos + 1)
+            }
+            Token::Ws => {
+                let len = self.run(pos, |c| c == ' ');
+                (len > 0 || self.fail(pos, "space")).then_some(pos + len)
+            }
+            Token::OptWs => Some(pos + self.run(pos, |c| c == ' ')),
+            Token::List { item, sep } => {
+                let mut pos = self.seq(item, pos)?;
+                // keep going while both a separator and another item follow
+                while let Some(next) = self.seq(sep, pos).and_then(|p| self.seq(item, p)) {
+                    pos = next;
+                }
+                Some(pos)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGE: &[Token] = &[Token::Uint, Token::Lit("-"), Token::Uint];
+
+    #[test]
+    fn test_lines() {
+        let schema = Schema::Lines(&[Token::OneOf("LR"), Token::Uint]);
+        assert!(schema.validate("L68\nR48\n").is_ok());
+
+        let errs = schema.validate("L68\nX48\nR4a").unwrap_err();
+        assert_eq!(
+            errs,
+            vec![
+                Violation::new(2, 1, "expected one of \"LR\", found 'X'"),
+                Violation::new(3, 3, "expected end of line, found 'a'"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extra_blank_line() {
+        let schema = Schema::Lines(&[Token::Uint]);
+        let errs = schema.validate("1\n2\n\n").unwrap_err();
+        assert_eq!(
+            errs,
+            vec![Violation::new(3, 1, "expected digit, found empty line")]
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        let schema = Schema::Lines(&[Token::List {
+            item: RANGE,
+            sep: &[Token::Lit(",")],
+        }]);
+        assert!(schema.validate("11-22,95-115\n").is_ok());
+
+        // error points at the bad character inside the second range, not at the comma
+        let errs = schema.validate("11-22,95-x").unwrap_err();
+        assert_eq!(errs[0].column, 10);
+    }
+
+    #[test]
+    fn test_trailer() {
+        let schema = Schema::Trailer {
+            body: &[
+                Token::OptWs,
+                Token::List {
+                    item: &[Token::Uint],
+                    sep: &[Token::Ws],
+                },
+                Token::OptWs,
+            ],
+            last: &[Token::OneOf("*+"), Token::OptWs],
+        };
+        assert!(schema.validate("1 2\n 3  4 \n*  ").is_ok());
+        assert_eq!(schema.validate("1 2\n-").unwrap_err()[0].line, 2);
+    }
+
+    #[test]
+    fn test_grid() {
+        let schema = Schema::Grid(".@");
+        assert!(schema.validate("..@\n@@.").is_ok());
+
+        let errs = schema.validate("..@\n@#.\n.@").unwrap_err();
+        assert_eq!(errs.len(), 2);
+        assert_eq!((errs[0].line, errs[0].column), (2, 2));
+        assert_eq!((errs[1].line, errs[1].column), (3, 3));
+    }
+
+    #[test]
+    fn test_sections() {
+        let schema = Schema::Sections(&[Schema::Lines(RANGE), Schema::Lines(&[Token::Uint])]);
+        assert!(schema.validate("3-5\n10-14\n\n1\n5").is_ok());
+
+        // missing split
+        let errs = schema.validate("3-5\n10-14\n1\n5").unwrap_err();
+        assert_eq!(
+            errs[0].message,
+            "expected 2 blank-line separated sections, found 1"
+        );
+
+        // line numbers are relative to the whole input
+        let errs = schema.validate("3-5\n\n1\nx").unwrap_err();
+        assert_eq!((errs[0].line, errs[0].column), (4, 1));
+
+        let errs = schema.validate("3-5\n\n\n1").unwrap_err();
+        assert_eq!(errs[0], Violation::new(3, 1, "unexpected blank line"));
+    }
+}
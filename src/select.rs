@@ -0,0 +1,223 @@
+//! Choosing which days to run: selection expressions like `1-5,8` and filters based on the
+//! run history.
+
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::history::{Fingerprints, History};
+
+/// Highest day an expression may name
+const LAST_DAY: u8 = 25;
+
+/// A set of days, parsed from comma-separated days and inclusive ranges like `1-5,8`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DaySet(BTreeSet<u8>);
+
+impl DaySet {
+    pub fn contains(&self, day: u8) -> bool {
+        self.0.contains(&day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl FromIterator<u8> for DaySet {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = String;
+
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        let parse_day = |s: &str| match s.trim().parse::<u8>() {
+            Ok(day @ 1..=LAST_DAY) => Ok(day),
+            _ => Err(format!(
+                "invalid day {:?}, expected 1-{}",
+                s.trim(),
+                LAST_DAY
+            )),
+        };
+
+        let mut days = BTreeSet::new();
+        for item in expr.split(',') {
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_day(start)?, parse_day(end)?);
+                    if start > end {
+                        return Err(format!("invalid range {:?}, start is after end", item));
+                    }
+                    days.extend(start..=end);
+                }
+                None => {
+                    days.insert(parse_day(item)?);
+                }
+            }
+        }
+        Ok(Self(days))
+    }
+}
+
+/// Renders back as a compact expression, `1-5,8`
+impl Display for DaySet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let days: Vec<u8> = self.iter().collect();
+        let runs = days.chunk_by(|a, b| a + 1 == *b);
+        let items: Vec<String> = runs
+            .map(|run| match run {
+                [day] => day.to_string(),
+                [first, .., last] => format!("{}-{}", first, last),
+                [] => unreachable!("chunks are never empty"),
+            })
+            .collect();
+        write!(f, "{}", items.join(","))
+    }
+}
+
+/// Filters narrowing down the selected days, applied in the order of the fields
+#[derive(Debug, Clone, Default)]
+pub struct Filters {
+    pub exclude: Option<DaySet>,
+    /// Keep days whose last verify run failed
+    pub only_failing: bool,
+    /// Keep days whose source or input changed since the last run
+    pub changed: bool,
+    /// Keep the N slowest days by recorded timings
+    pub slowest: Option<usize>,
+}
+
+/// Apply the filters to `days`, returning the days to run in order.
+/// `fingerprints` gives the current source and input fingerprints of a day for `changed`.
+pub fn select(
+    days: impl IntoIterator<Item = u8>,
+    filters: &Filters,
+    history: &History,
+    fingerprints: impl Fn(u8) -> Option<Fingerprints>,
+) -> Vec<u8> {
+    let mut days: Vec<u8> = days
+        .into_iter()
+        .filter(|&day| !filters.exclude.as_ref().is_some_and(|ex| ex.contains(day)))
+        .filter(|&day| {
+            !filters.only_failing || history.get(day).and_then(|r| r.passed) == Some(false)
+        })
+        .filter(|&day| !filters.changed || history.changed(day, fingerprints(day)))
+        .collect();
+
+    if let Some(n) = filters.slowest {
+        let elapsed = |day| history.get(day).and_then(|r| r.elapsed_ms);
+        let mut timed: Vec<(u8, f64)> = days
+            .iter()
+            .filter_map(|&day| Some((day, elapsed(day)?)))
+            .collect();
+        timed.sort_by(|a, b| b.1.total_cmp(&a.1));
+        days = timed.into_iter().take(n).map(|(day, _)| day).collect();
+        days.sort();
+    }
+
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::DayRecord;
+
+    fn days(expr: &str) -> Vec<u8> {
+        expr.parse::<DaySet>().unwrap().iter().collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(days("3"), [3]);
+        assert_eq!(days("1-5,8"), [1, 2, 3, 4, 5, 8]);
+        assert_eq!(days("8, 2-3,2"), [2, 3, 8]);
+        assert!("0".parse::<DaySet>().is_err());
+        assert!("26".parse::<DaySet>().is_err());
+        assert!("5-1".parse::<DaySet>().is_err());
+        assert!("1-".parse::<DaySet>().is_err());
+        assert!("".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let set: DaySet = "1-5,8,10-11".parse().unwrap();
+        assert_eq!(set.to_string(), "1-5,8,10-11");
+    }
+
+    fn history() -> History {
+        let record = |passed, elapsed_ms| DayRecord {
+            passed,
+            elapsed_ms,
+            fingerprints: None,
+        };
+        History {
+            days: [
+                (1, record(Some(true), Some(0.5))),
+                (2, record(Some(false), Some(40.0))),
+                (3, record(None, Some(12.0))),
+                (4, record(Some(false), None)),
+            ]
+            .into(),
+        }
+    }
+
+    #[test]
+    fn test_filters() {
+        let history = history();
+        let no_prints = |_| None;
+        let run = |filters: Filters| select(1..=5, &filters, &history, no_prints);
+
+        let exclude = Some("2-3".parse().unwrap());
+        assert_eq!(
+            run(Filters {
+                exclude,
+                ..Filters::default()
+            }),
+            [1, 4, 5]
+        );
+        assert_eq!(
+            run(Filters {
+                only_failing: true,
+                ..Filters::default()
+            }),
+            [2, 4]
+        );
+        // days without timings can't be ranked
+        assert_eq!(
+            run(Filters {
+                slowest: Some(2),
+                ..Filters::default()
+            }),
+            [2, 3]
+        );
+        assert_eq!(
+            run(Filters {
+                only_failing: true,
+                slowest: Some(1),
+                ..Filters::default()
+            }),
+            [2]
+        );
+    }
+
+    #[test]
+    fn test_changed() {
+        let prints = Fingerprints {
+            source: 1,
+            input: 1,
+        };
+        let mut history = history();
+        history.days.get_mut(&1).unwrap().fingerprints = Some(prints);
+        let filters = Filters {
+            changed: true,
+            ..Filters::default()
+        };
+        // day 1 is unchanged, day 2 was never fingerprinted, day 3 has no input
+        let selected = select(1..=3, &filters, &history, |day| (day < 3).then_some(prints));
+        assert_eq!(selected, [2]);
+    }
+}
@@ -0,0 +1,631 @@
+//! Minimal local HTTP server exposing the solvers as a JSON API, for `serve`.
+//!
+//! - `GET /days` lists the registered days
+//! - `POST /solve/{day}[?part=N]` solves the request body as the day's input
+//! - `POST /verify/{day}[?part=N]` solves and checks the answers against `answers.txt`
+//!
+//! Each connection is handled on its own thread and closed after one response. Request
+//! headers and bodies are size limited and must arrive within the read timeout, and solving is abandoned with a 504 after the
+//! configured timeout. A timed out solver can't be interrupted, its thread runs to
+//! completion in the background and keeps its solve slot until then: once all slots are taken,
+//! further solves are rejected with a 503, and so are connections beyond their own limit.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+use crate::answers::{Answers, load_answers};
+use crate::runner::{self, DayResult, Outcome};
+use crate::{days, get_day_solvers};
+
+/// Largest request line plus headers accepted
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+/// Pause after a failed accept, so that running out of file descriptors doesn't spin
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Largest request body accepted, in bytes
+    pub max_body: usize,
+    /// Time allowed for receiving the whole request, however slowly it trickles in
+    pub read_timeout: Duration,
+    /// Time allowed for solving, after which the request fails with a 504
+    pub solve_timeout: Duration,
+    /// Stored answers used by `/verify`
+    pub answers_file: PathBuf,
+    /// Connections handled at once, more are turned away with a 503
+    pub max_connections: usize,
+    /// Solves running at once, including timed out ones that haven't finished yet
+    pub max_solves: usize,
+    /// Value of `Access-Control-Allow-Origin`, to allow a dashboard served elsewhere.
+    /// Off by default, since `/verify` reveals the stored answers.
+    pub allow_origin: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            max_body: 1024 * 1024,
+            read_timeout: Duration::from_secs(5),
+            solve_timeout: Duration::from_secs(10),
+            answers_file: PathBuf::from(crate::answers::ANSWERS_FILE),
+            max_connections: 64,
+            max_solves: thread::available_parallelism().map_or(1, |n| n.get()),
+            allow_origin: None,
+        }
+    }
+}
+
+pub struct Server {
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+    connections: Slots,
+    solves: Slots,
+}
+
+/// A fixed number of slots shared between threads
+#[derive(Debug, Clone)]
+struct Slots {
+    used: Arc<AtomicUsize>,
+    max: usize,
+}
+
+/// A taken slot, given back on drop
+#[derive(Debug)]
+struct Permit(Arc<AtomicUsize>);
+
+impl Slots {
+    fn new(max: usize) -> Self {
+        Self {
+            used: Arc::new(AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    /// Take a slot, None if all are taken
+    fn try_acquire(&self) -> Option<Permit> {
+        self.used
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used| {
+                (used < self.max).then_some(used + 1)
+            })
+            .ok()
+            .map(|_| Permit(Arc::clone(&self.used)))
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl Server {
+    pub fn bind(addr: impl ToSocketAddrs, config: ServerConfig) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            connections: Slots::new(config.max_connections),
+            solves: Slots::new(config.max_solves),
+            config: Arc::new(config),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept connections for good. A failed accept only loses that connection: it is
+    /// reported and the server carries on.
+    pub fn run(&self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("cannot accept a connection: {}", err);
+                    thread::sleep(ACCEPT_BACKOFF);
+                    continue;
+                }
+            };
+            let Some(permit) = self.connections.try_acquire() else {
+                turn_away(&stream, &self.config);
+                continue;
+            };
+            let config = Arc::clone(&self.config);
+            let solves = self.solves.clone();
+            thread::spawn(move || {
+                handle_connection(stream, &config, &solves);
+                drop(permit);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Answer a 503 on the accepting thread, briefly, since there is no thread to spare. The
+/// request is read, up to a point, so that closing doesn't reset the connection before the
+/// client sees the answer.
+fn turn_away(mut stream: &TcpStream, config: &ServerConfig) {
+    let brief = Some(Duration::from_millis(50));
+    let _ = stream.set_write_timeout(brief);
+    let _ = stream.set_read_timeout(brief);
+    let busy = Response::error(503, "too many connections");
+    let _ = write_response(stream, &busy, config);
+    let _ = stream.shutdown(std::net::Shutdown::Write);
+    let _ = stream.read(&mut [0; MAX_HEADER_BYTES]);
+}
+
+/// A JSON response
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    query: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Reads from a stream until a deadline, by shortening the socket's read timeout to the time
+/// left before each read
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    until: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+fn handle_connection(stream: TcpStream, config: &ServerConfig, solves: &Slots) {
+    let mut reader = BufReader::new(Deadline {
+        stream: &stream,
+        until: Instant::now() + config.read_timeout,
+    });
+    let response = match read_request(&mut reader, config.max_body) {
+        Ok(request) => route(&request, config, solves),
+        Err(response) => response,
+    };
+    // the client may already be gone, nothing to do about it
+    let _ = write_response(&stream, &response, config);
+}
+
+fn io_error(err: io::Error) -> Response {
+    match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            Response::error(408, "timed out reading the request")
+        }
+        _ => Response::error(400, format!("cannot read request: {}", err)),
+    }
+}
+
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, Response> {
+    let mut head = Vec::new();
+    let mut header_bytes = 0;
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .by_ref()
+            .take((MAX_HEADER_BYTES - header_bytes + 1) as u64)
+            .read_line(&mut line)
+            .map_err(io_error)?;
+        header_bytes += read;
+        if header_bytes > MAX_HEADER_BYTES {
+            return Err(Response::error(431, "request headers are too large"));
+        }
+        if read == 0 {
+            return Err(Response::error(400, "incomplete request"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        head.push(line.to_string());
+    }
+
+    let Some(request_line) = head.first() else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+    let mut request_line = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query.to_string())),
+        None => (target, None),
+    };
+
+    let header = |name: &str| {
+        head[1..].iter().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().to_string())
+        })
+    };
+    if header("transfer-encoding").is_some() {
+        return Err(Response::error(411, "chunked bodies aren't supported"));
+    }
+    let length = match header("content-length") {
+        Some(length) => length
+            .parse::<usize>()
+            .map_err(|_| Response::error(400, "invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > max_body {
+        return Err(Response::error(
+            413,
+            format!("body is {} bytes, the limit is {}", length, max_body),
+        ));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(io_error)?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        body,
+    })
+}
+
+fn write_response(
+    mut stream: &TcpStream,
+    response: &Response,
+    config: &ServerConfig,
+) -> io::Result<()> {
+    let body = response.body.to_string();
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    if let Some(origin) = &config.allow_origin {
+        head.push_str(&format!("Access-Control-Allow-Origin: {}\r\n", origin));
+    }
+    write!(stream, "{}\r\n{}", head, body)?;
+    stream.flush()
+}
+
+fn route(request: &Request, config: &ServerConfig, solves: &Slots) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => {
+            let days: Vec<u8> = days().iter().map(|solvers| solvers.day).collect();
+            Response::ok(json!({ "days": days }))
+        }
+        ("POST", ["solve", day]) => solve(request, day, config, solves, None),
+        ("POST", ["verify", day]) => match load_answers(&config.answers_file) {
+            Ok(answers) => {
+                let expected = day
+                    .parse()
+                    .ok()
+                    .and_then(|day| answers.get(&day).cloned())
+                    .unwrap_or_default();
+                solve(request, day, config, solves, Some(&expected))
+            }
+            Err(err) => Response::error(500, format!("cannot read answers: {}", err)),
+        },
+        (_, ["days"] | ["solve", _] | ["verify", _]) => {
+            Response::error(405, format!("{} is not allowed here", request.method))
+        }
+        _ => Response::error(404, format!("no route for {}", request.path)),
+    }
+}
+
+/// Solve the request body as the day's input, checking answers if `expected` is given
+fn solve(
+    request: &Request,
+    day: &str,
+    config: &ServerConfig,
+    solves: &Slots,
+    expected: Option<&Answers>,
+) -> Response {
+    let Some(day) = day
+        .parse()
+        .ok()
+        .filter(|&day| get_day_solvers(day).is_some())
+    else {
+        return Response::error(404, format!("day {} is not implemented", day));
+    };
+    let part = match query_param(request.query.as_deref(), "part") {
+        None => None,
+        Some("1") => Some(1),
+        Some("2") => Some(2),
+        Some(part) => return Response::error(400, format!("invalid part {:?}", part)),
+    };
+    let Ok(input) = String::from_utf8(request.body.clone()) else {
+        return Response::error(400, "input is not valid UTF-8");
+    };
+
+    let Some(permit) = solves.try_acquire() else {
+        return Response::error(503, "too many solves running, try again later");
+    };
+    // solve on another thread so a slow solver can be abandoned, it keeps the slot until done
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(runner::solve(day, &input, part));
+        drop(permit);
+    });
+    match receiver.recv_timeout(config.solve_timeout) {
+        Ok(result) => result_response(&result, expected),
+        Err(_) => Response::error(
+            504,
+            format!(
+                "solving took longer than {} ms",
+                config.solve_timeout.as_millis()
+            ),
+        ),
+    }
+}
+
+fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+}
+
+fn result_response(result: &DayResult, expected: Option<&Answers>) -> Response {
+    let (status, errors) = match &result.outcome {
+        Outcome::Solved { .. } => ("solved", Vec::new()),
+        Outcome::InvalidInput(errors) => ("invalid_input", errors.iter().collect()),
+        Outcome::Failed(err) => ("failed", vec![err]),
+        Outcome::Skipped => unreachable!("the input is always given"),
+    };
+
+    let parts: Vec<Value> = result
+        .answers()
+        .map(|(part, sol)| {
+            let mut value = json!({
+                "part": part,
+                "answer": sol.to_string(),
+                "variant": sol.variant(),
+            });
+            if let Some(expected) = expected {
+                let stored = expected.part(part);
+                value["expected"] = json!((!stored.is_empty()).then_some(stored));
+                value["passed"] = json!(expected.matches(part, sol));
+            }
+            value
+        })
+        .collect();
+
+    let mut body = json!({
+        "day": result.day,
+        "status": status,
+        "elapsed_ms": result.elapsed_ms(),
+        "parts": parts,
+    });
+    if !errors.is_empty() {
+        body["errors"] = json!(errors.iter().map(|e| e.to_string()).collect::<Vec<_>>());
+    }
+    if let Some(expected) = expected {
+        body["passed"] = json!(result.passed(Some(expected)));
+    }
+
+    let status_code = if errors.is_empty() { 200 } else { 422 };
+    Response {
+        status: status_code,
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    fn start(config: ServerConfig) -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        addr
+    }
+
+    /// Send a raw request, returning the status code and JSON body
+    fn send(addr: SocketAddr, request: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn post(addr: SocketAddr, path: &str, body: &str) -> (u16, Value) {
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            path,
+            body.len(),
+            body
+        );
+        send(addr, &request)
+    }
+
+    #[test]
+    fn test_days() {
+        let addr = start(ServerConfig::default());
+        let (status, body) = send(addr, "GET /days HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 200);
+        assert_eq!(body["days"][0], 1);
+    }
+
+    #[test]
+    fn test_solve() {
+        let addr = start(ServerConfig::default());
+        let (status, body) = post(addr, "/solve/1", DAY1_INPUT);
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "solved");
+        assert_eq!(body["parts"][0]["answer"], "3");
+        assert_eq!(body["parts"][1]["answer"], "6");
+        assert_eq!(body["parts"][1]["variant"], "Usize");
+        assert!(body["elapsed_ms"].as_f64().is_some());
+
+        let (_, body) = post(addr, "/solve/1?part=2", DAY1_INPUT);
+        assert_eq!(body["parts"].as_array().unwrap().len(), 1);
+        assert_eq!(body["parts"][0]["part"], 2);
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let addr = start(ServerConfig::default());
+        let (status, body) = post(addr, "/solve/1", "L68\nX30");
+        assert_eq!(status, 422);
+        assert_eq!(body["status"], "invalid_input");
+        assert!(body["errors"][0].as_str().unwrap().contains("line 2"));
+
+        assert_eq!(post(addr, "/solve/99", "").0, 404);
+        assert_eq!(post(addr, "/solve/1?part=3", DAY1_INPUT).0, 400);
+        assert_eq!(send(addr, "GET /solve/1 HTTP/1.1\r\n\r\n").0, 405);
+        assert_eq!(send(addr, "GET /nope HTTP/1.1\r\n\r\n").0, 404);
+    }
+
+    #[test]
+    fn test_verify() {
+        let dir = std::env::temp_dir().join(format!("server-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let answers_file = dir.join("answers.txt");
+        std::fs::write(&answers_file, "1: 3, 7\n").unwrap();
+
+        let addr = start(ServerConfig {
+            answers_file,
+            ..ServerConfig::default()
+        });
+        let (status, body) = post(addr, "/verify/1", DAY1_INPUT);
+        assert_eq!(status, 200);
+        assert_eq!(body["passed"], false);
+        assert_eq!(body["parts"][0]["passed"], true);
+        assert_eq!(body["parts"][1]["passed"], false);
+        assert_eq!(body["parts"][1]["expected"], "7");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_limits() {
+        let addr = start(ServerConfig {
+            max_body: 8,
+            read_timeout: Duration::from_millis(50),
+            ..ServerConfig::default()
+        });
+        assert_eq!(post(addr, "/solve/1", DAY1_INPUT).0, 413);
+        // announced body never arrives
+        let (status, _) = send(
+            addr,
+            "POST /solve/1 HTTP/1.1\r\nContent-Length: 5\r\n\r\nL6",
+        );
+        assert_eq!(status, 408);
+        let huge = format!(
+            "GET /days HTTP/1.1\r\nX: {}\r\n\r\n",
+            "a".repeat(MAX_HEADER_BYTES)
+        );
+        assert_eq!(send(addr, &huge).0, 431);
+        assert_eq!(send(addr, "\r\n").0, 400);
+    }
+
+    #[test]
+    fn test_deadline() {
+        // each byte arrives well within the timeout, the whole request doesn't
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let trickle = thread::spawn(move || {
+            for byte in b"GET /days HTTP/1.1\r\nX: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa" {
+                if client.write_all(&[*byte]).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let start = Instant::now();
+        let mut reader = BufReader::new(Deadline {
+            stream: &stream,
+            until: start + Duration::from_millis(100),
+        });
+        let response = read_request(&mut reader, 0).unwrap_err();
+        assert_eq!(response.status, 408);
+        assert!(start.elapsed() < Duration::from_millis(300));
+        trickle.join().unwrap();
+    }
+
+    #[test]
+    fn test_solve_timeout() {
+        let addr = start(ServerConfig {
+            solve_timeout: Duration::ZERO,
+            ..ServerConfig::default()
+        });
+        assert_eq!(post(addr, "/solve/1", DAY1_INPUT).0, 504);
+    }
+
+    #[test]
+    fn test_slots() {
+        let slots = Slots::new(2);
+        let first = slots.try_acquire().unwrap();
+        let _second = slots.try_acquire().unwrap();
+        assert!(slots.try_acquire().is_none());
+        drop(first);
+        assert!(slots.try_acquire().is_some());
+
+        let addr = start(ServerConfig {
+            max_solves: 0,
+            ..ServerConfig::default()
+        });
+        assert_eq!(post(addr, "/solve/1", DAY1_INPUT).0, 503);
+        assert_eq!(send(addr, "GET /days HTTP/1.1\r\n\r\n").0, 200);
+
+        let addr = start(ServerConfig {
+            max_connections: 0,
+            ..ServerConfig::default()
+        });
+        assert_eq!(send(addr, "GET /days HTTP/1.1\r\n\r\n").0, 503);
+    }
+}
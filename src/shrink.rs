@@ -0,0 +1,320 @@
+//! Shrinking an input that shows a bug down to a small reproducer, by delta debugging: blank-
+//! line separated sections, then lines, then the columns of a grid are removed in ever smaller
+//! chunks for as long as the bug still shows.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::OracleConfig;
+use crate::oracle::{self, InputFile};
+use crate::runner;
+use crate::solution::Solution;
+
+/// Longest shrunk input printed by [`Shrunk::render`], longer ones are only written
+const MAX_LINES_SHOWN: usize = 40;
+
+/// What makes an input interesting: the bug being chased
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    /// The part solvers and the combined solver give different answers
+    Split,
+    /// Our answer differs from the oracle's, see [`oracle`]
+    Oracle(String),
+    /// A shell command exits successfully. `{day}`, `{part}` and `{input}` are filled in as
+    /// for oracles and the input is on stdin.
+    Command(String),
+}
+
+impl Predicate {
+    /// Oracle predicate with the template from the command line or the config
+    pub fn oracle(cli: Option<&str>, config: &OracleConfig, day: u8) -> Option<Self> {
+        oracle::template(cli, config, day).map(|template| Predicate::Oracle(template.into()))
+    }
+
+    /// Whether `input` still shows the bug in `part` of `day` (either part if None). Inputs
+    /// on which a solver fails don't count, so that shrinking keeps the disagreement rather
+    /// than drifting to an input that merely breaks a parser. Oracles and commands are killed
+    /// after `timeout`.
+    pub fn holds(&self, day: u8, part: Option<u8>, input: &str, timeout: Duration) -> bool {
+        let parts = match part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+        match self {
+            Predicate::Split => {
+                let both = runner::solve(day, input, None);
+                parts.into_iter().any(|part| {
+                    let single = runner::solve(day, input, Some(part));
+                    disagree(both.answer(part).ok(), single.answer(part).ok())
+                })
+            }
+            Predicate::Oracle(template) => {
+                let Ok(file) = InputFile::temporary(day, input) else {
+                    return false;
+                };
+                parts.into_iter().any(|part| {
+                    let comparison = oracle::compare(day, part, &file, input, template, timeout);
+                    comparison.ours.is_ok()
+                        && comparison.oracle.output.is_ok()
+                        && !comparison.agrees()
+                })
+            }
+            Predicate::Command(command) => {
+                let Ok(file) = InputFile::temporary(day, input) else {
+                    return false;
+                };
+                let command = oracle::expand(command, day, part.unwrap_or(1), file.path());
+                oracle::run(&command, input, timeout).output.is_ok()
+            }
+        }
+    }
+
+    /// Shrink `input` for as long as the bug shows in `part` of `day`. Fails if it doesn't
+    /// show on `input` itself.
+    pub fn shrink(
+        &self,
+        day: u8,
+        part: Option<u8>,
+        input: &str,
+        timeout: Duration,
+    ) -> Result<Shrunk, String> {
+        if !self.holds(day, part, input, timeout) {
+            return Err("the bug doesn't show on the input, nothing to shrink".into());
+        }
+        Ok(shrink(input, |candidate| {
+            self.holds(day, part, candidate, timeout)
+        }))
+    }
+}
+
+fn disagree(a: Option<&Solution>, b: Option<&Solution>) -> bool {
+    matches!((a, b), (Some(a), Some(b)) if !a.matches(b))
+}
+
+/// Result of shrinking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shrunk {
+    pub input: String,
+    /// How many candidate inputs were tested
+    pub tests: usize,
+}
+
+impl Shrunk {
+    /// Save the input as the next example file of `day`, returning its path
+    pub fn save(&self, day: u8) -> io::Result<PathBuf> {
+        let path = runner::new_example_path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &self.input)?;
+        Ok(path)
+    }
+
+    /// What `shrink` prints once the input shrunk from `original` is saved at `path`, with
+    /// the input itself if it is short
+    pub fn render(&self, day: u8, original: &str, path: &Path) -> String {
+        let lines = self.input.lines().count();
+        let mut text = format!(
+            "Shrunk day {} input from {} to {} lines in {} tests, written to {}\n",
+            day,
+            original.lines().count(),
+            lines,
+            self.tests,
+            path.display()
+        );
+        if lines <= MAX_LINES_SHOWN {
+            text += &format!("\n{}\n", self.input.trim_end());
+        }
+        text
+    }
+}
+
+/// Remove as many `units` as possible while `test` holds for the rest, trying to drop
+/// complements of ever smaller chunks (ddmin). The result is 1-minimal: removing any single
+/// unit makes `test` fail.
+pub fn reduce<T: Clone>(mut units: Vec<T>, mut test: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunks = 2;
+    while !units.is_empty() {
+        let size = units.len().div_ceil(chunks);
+        let mut removed = false;
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + size).min(units.len());
+            let candidate = [&units[..start], &units[end..]].concat();
+            if test(&candidate) {
+                units = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        match (removed, size) {
+            (true, _) => chunks = (chunks - 1).max(2),
+            (false, 1) => break,
+            (false, _) => chunks = (chunks * 2).min(units.len()),
+        }
+    }
+    units
+}
+
+/// Shrink `input` while `test` holds, which it must for `input` itself. Passes over
+/// sections, lines and grid columns repeat until none of them removes anything.
+pub fn shrink(input: &str, mut test: impl FnMut(&str) -> bool) -> Shrunk {
+    let newline = if input.ends_with('\n') { "\n" } else { "" };
+    let mut tests = 0;
+    let mut check = |candidate: &str| {
+        tests += 1;
+        test(candidate)
+    };
+
+    let mut lines: Vec<String> = input.lines().map(str::to_string).collect();
+    loop {
+        let before = lines.clone();
+
+        let sections: Vec<Vec<String>> = lines
+            .split(|line| line.is_empty())
+            .map(<[String]>::to_vec)
+            .collect();
+        if sections.len() > 1 {
+            let render = |sections: &[Vec<String>]| {
+                let lines: Vec<String> = sections.join(&String::new());
+                lines.join("\n") + newline
+            };
+            let kept = reduce(sections, |sections| check(&render(sections)));
+            lines = kept.join(&String::new());
+        }
+
+        // blank lines stay, they separate the remaining sections
+        let removable: Vec<usize> = (0..lines.len()).filter(|&i| !lines[i].is_empty()).collect();
+        let render = |kept: &BTreeSet<usize>| {
+            let lines: Vec<&str> = (0..lines.len())
+                .filter(|i| lines[*i].is_empty() || kept.contains(i))
+                .map(|i| lines[i].as_str())
+                .collect();
+            lines.join("\n") + newline
+        };
+        let kept: BTreeSet<usize> = reduce(removable, |kept| {
+            check(&render(&kept.iter().copied().collect()))
+        })
+        .into_iter()
+        .collect();
+        lines = (0..lines.len())
+            .filter(|i| lines[*i].is_empty() || kept.contains(i))
+            .map(|i| lines[i].clone())
+            .collect();
+
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let grid = lines.len() > 1 && lines.iter().all(|line| line.chars().count() == width);
+        if grid && width > 1 {
+            let rows: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+            let render = |columns: &[usize]| -> Vec<String> {
+                rows.iter()
+                    .map(|row| columns.iter().map(|&c| row[c]).collect())
+                    .collect()
+            };
+            let columns = reduce((0..width).collect(), |columns| {
+                check(&(render(columns).join("\n") + newline))
+            });
+            lines = render(&columns);
+        }
+
+        if lines == before {
+            break;
+        }
+    }
+    Shrunk {
+        input: lines.join("\n") + newline,
+        tests,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduce() {
+        let units: Vec<u32> = (0..20).collect();
+        let kept = reduce(units, |units| units.contains(&3) && units.contains(&17));
+        assert_eq!(kept, [3, 17]);
+
+        let all = reduce(vec![1, 2, 3], |units| units.len() == 3);
+        assert_eq!(all, [1, 2, 3]);
+        assert!(reduce(vec![1, 2], |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_lines() {
+        let input: String = (0..50).map(|i| format!("{}\n", i)).collect();
+        let shrunk = shrink(&input, |text| {
+            let numbers: Vec<u32> = text.lines().map(|line| line.parse().unwrap()).collect();
+            numbers.contains(&7) && numbers.contains(&42)
+        });
+        assert_eq!(shrunk.input, "7\n42\n");
+        assert!(shrunk.tests > 0);
+    }
+
+    #[test]
+    fn test_shrink_sections() {
+        let input = "a\nb\n\nc\nbug\nd\n\ne";
+        let shrunk = shrink(input, |text| text.contains("bug") && text.contains('a'));
+        assert_eq!(shrunk.input, "a\n\nbug");
+    }
+
+    #[test]
+    fn test_shrink_grid() {
+        let input = "....\n.#..\n....\n...#\n";
+        let shrunk = shrink(input, |text| {
+            let rows: Vec<&str> = text.lines().collect();
+            rows.len() >= 2 && rows.iter().filter(|row| row.contains('#')).count() >= 2
+        });
+        assert_eq!(shrunk.input, "#.\n.#\n");
+    }
+
+    #[test]
+    fn test_render() {
+        let shrunk = Shrunk {
+            input: "7\n42\n".into(),
+            tests: 12,
+        };
+        let original: String = (0..50).map(|i| format!("{}\n", i)).collect();
+        let path = Path::new("input/day1/example2.txt");
+        assert_eq!(
+            shrunk.render(1, &original, path),
+            "Shrunk day 1 input from 50 to 2 lines in 12 tests, written to \
+             input/day1/example2.txt\n\n7\n42\n"
+        );
+        let long = Shrunk {
+            input: original.clone(),
+            tests: 1,
+        };
+        assert_eq!(long.render(1, &original, path).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_predicates() {
+        let day1 = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        let holds = |predicate: Predicate, part, input| {
+            predicate.holds(1, part, input, oracle::DEFAULT_TIMEOUT)
+        };
+        assert!(!holds(Predicate::Split, None, day1));
+        assert!(!holds(Predicate::Split, None, "X1"));
+        let error = Predicate::Split.shrink(1, None, day1, oracle::DEFAULT_TIMEOUT);
+        assert!(error.unwrap_err().contains("doesn't show"));
+
+        #[cfg(unix)]
+        {
+            assert!(holds(Predicate::Oracle("echo 4".into()), Some(1), day1));
+            assert!(!holds(Predicate::Oracle("echo 3".into()), Some(1), day1));
+            assert!(holds(
+                Predicate::Command("grep -q L99 {input}".into()),
+                None,
+                day1
+            ));
+            assert!(!holds(Predicate::Command("grep -q L99".into()), None, "L1"));
+        }
+    }
+}
@@ -1,24 +1,47 @@
+use crate::bigint::BigInt;
+use crate::error::SolveError;
+use crate::grid::Grid;
+use crate::ocr;
+use crate::schema::Schema;
 use Solution::*;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::fmt::{Display, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// Relative tolerance used when verifying float answers
+const FLOAT_TOLERANCE: f64 = 1e-9;
 
 pub type SolutionPair = (Solution, Solution);
 
+/// Result of solving a single part
+pub type SolveResult = std::result::Result<Solution, SolveError>;
+
 /// Trait that all day solutions must implement.
 /// Enforces a consistent structure across all days.
 pub trait Day {
+    /// Expected shape of the input, checked before any part is solved
+    const SCHEMA: Schema = Schema::Any;
+
     /// Solve part 1 of the puzzle
-    fn part1(input: &str) -> Solution;
+    fn part1(input: &str) -> SolveResult;
 
     /// Solve part 2 of the puzzle
-    fn part2(input: &str) -> Solution;
+    fn part2(input: &str) -> SolveResult;
 
     /// Solve both parts (default implementation calls part1 and part2)
-    fn solve(input: &str) -> SolutionPair {
-        (Self::part1(input), Self::part2(input))
+    fn solve(input: &str) -> std::result::Result<SolutionPair, SolveError> {
+        Ok((Self::part1(input)?, Self::part2(input)?))
     }
 }
 
-#[derive(Clone)]
+/// Answer to a puzzle part.
+///
+/// Equality and hashing are by value: integer variants of any width are equal when they hold
+/// the same number, and a float equals an integer if it is exactly integral. Floats are only
+/// compared with a tolerance by [`Solution::matches`], since that isn't transitive.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Solution {
     I8(i8),
     I16(i16),
@@ -32,7 +55,172 @@ pub enum Solution {
     U64(u64),
     U128(u128),
     Usize(usize),
+    Big(BigInt),
+    Float(f64),
     Str(String),
+    /// Letters drawn on a grid, lit cells are `true`
+    Picture(Grid<bool>),
+}
+
+impl Solution {
+    /// Name of the variant, like `"U64"` or `"Picture"`
+    pub fn variant(&self) -> &'static str {
+        match self {
+            I8(_) => "I8",
+            I16(_) => "I16",
+            I32(_) => "I32",
+            I64(_) => "I64",
+            I128(_) => "I128",
+            Isize(_) => "Isize",
+            U8(_) => "U8",
+            U16(_) => "U16",
+            U32(_) => "U32",
+            U64(_) => "U64",
+            U128(_) => "U128",
+            Usize(_) => "Usize",
+            Big(_) => "Big",
+            Float(_) => "Float",
+            Str(_) => "Str",
+            Picture(_) => "Picture",
+        }
+    }
+
+    /// The drawing of a `Picture` answer, rendered with '#' and '.'
+    pub fn picture(&self) -> Option<String> {
+        match self {
+            Picture(grid) => Some(grid.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Whether this answer matches an expected one, as used by `--verify`.
+    /// Numbers are compared by value, floats within a relative tolerance, and anything else
+    /// by its text, so a picture matches the letters it spells.
+    pub fn matches(&self, expected: &Solution) -> bool {
+        let has_float = matches!(self, Float(_)) || matches!(expected, Float(_));
+        if has_float && let (Some(a), Some(b)) = (self.as_f64(), expected.as_f64()) {
+            return (a - b).abs() <= FLOAT_TOLERANCE * a.abs().max(b.abs()).max(1.0);
+        }
+
+        self == expected || self.to_string() == expected.to_string()
+    }
+
+    /// Value of an integer variant
+    fn integer(&self) -> Option<BigInt> {
+        Some(match self {
+            I8(x) => BigInt::from(*x),
+            I16(x) => BigInt::from(*x),
+            I32(x) => BigInt::from(*x),
+            I64(x) => BigInt::from(*x),
+            I128(x) => BigInt::from(*x),
+            Isize(x) => BigInt::from(*x),
+            U8(x) => BigInt::from(*x),
+            U16(x) => BigInt::from(*x),
+            U32(x) => BigInt::from(*x),
+            U64(x) => BigInt::from(*x),
+            U128(x) => BigInt::from(*x),
+            Usize(x) => BigInt::from(*x),
+            Big(x) => x.clone(),
+            _ => return None,
+        })
+    }
+
+    /// Value of any numeric variant as a float
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Float(x) => Some(*x),
+            _ => self.integer().and_then(|n| n.to_string().parse().ok()),
+        }
+    }
+
+    /// Canonical numeric value, shared by equality and hashing
+    fn numeric(&self) -> Option<Numeric> {
+        match self {
+            Float(x) if x.is_finite() && x.fract() == 0.0 => {
+                format!("{x:.0}").parse().ok().map(Numeric::Int)
+            }
+            // all NaNs are the same answer
+            Float(x) if x.is_nan() => Some(Numeric::Float(f64::NAN.to_bits())),
+            Float(x) => Some(Numeric::Float(x.to_bits())),
+            _ => self.integer().map(Numeric::Int),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum Numeric {
+    Int(BigInt),
+    /// Bits of a float that isn't integral
+    Float(u64),
+}
+
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.numeric(), other.numeric()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => match (self, other) {
+                (Str(a), Str(b)) => a == b,
+                (Picture(a), Picture(b)) => a == b,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Solution {}
+
+impl Hash for Solution {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match (self.numeric(), self) {
+            (Some(n), _) => (0u8, n).hash(state),
+            (None, Str(s)) => (1u8, s).hash(state),
+            (None, Picture(grid)) => (2u8, grid).hash(state),
+            (None, _) => unreachable!("all other variants are numeric"),
+        }
+    }
+}
+
+/// Parse an answer, inferring the variant: the narrowest of `I64`, `U64`, `I128`, `U128` and
+/// `Big` for integers, `Float` for decimals, `Picture` for rows of '#'/'.' joined by '/'
+/// (as pictures are displayed when they can't be read), and `Str` for anything else.
+impl FromStr for Solution {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(x) = s.parse::<i64>() {
+            return Ok(I64(x));
+        }
+        if let Ok(x) = s.parse::<u64>() {
+            return Ok(U64(x));
+        }
+        if let Ok(x) = s.parse::<i128>() {
+            return Ok(I128(x));
+        }
+        if let Ok(x) = s.parse::<u128>() {
+            return Ok(U128(x));
+        }
+        if let Ok(x) = s.parse::<BigInt>() {
+            return Ok(Big(x));
+        }
+        // f64 also accepts words like "inf" and "NaN", which are more likely to be text
+        if s.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.')
+            && let Ok(x) = s.parse::<f64>()
+        {
+            return Ok(Float(x));
+        }
+        if s.contains('/') && s.chars().all(|c| matches!(c, '#' | '.' | '/')) {
+            let rows: Vec<Vec<bool>> = s
+                .split('/')
+                .map(|row| row.chars().map(|c| c == '#').collect())
+                .collect();
+            if rows.iter().all(|row| row.len() == rows[0].len()) {
+                return Ok(Picture(Grid::from_rows(rows)));
+            }
+        }
+        Ok(Str(s.to_string()))
+    }
 }
 
 impl Display for Solution {
@@ -50,13 +238,21 @@ impl Display for Solution {
             U64(x) => x.fmt(f),
             U128(x) => x.fmt(f),
             Usize(x) => x.fmt(f),
+            Big(x) => x.fmt(f),
+            Float(x) => x.fmt(f),
             Str(x) => x.fmt(f),
+            // Pictures show as the letters they spell, so they fit on one line of answers.txt.
+            // If OCR can't read them, fall back to the rows joined by '/'.
+            Picture(grid) => match ocr::recognize(grid) {
+                Some(text) => text.fmt(f),
+                None => grid.to_string().replace('\n', "/").fmt(f),
+            },
         }
     }
 }
 
 macro_rules! impl_from {
-    ($type_:ident, $kind_:ident) => {
+    ($type_:ty, $kind_:ident) => {
         impl From<$type_> for Solution {
             fn from(sol: $type_) -> Self {
                 Self::$kind_(sol)
@@ -77,10 +273,155 @@ impl_from!(u32, U32);
 impl_from!(u64, U64);
 impl_from!(u128, U128);
 impl_from!(usize, Usize);
+impl_from!(BigInt, Big);
+impl_from!(f64, Float);
 impl_from!(String, Str);
 
+impl_from!(Grid<bool>, Picture);
+
+/// Convert a drawing of '#' characters into a picture
+impl From<Grid<u8>> for Solution {
+    fn from(grid: Grid<u8>) -> Self {
+        let rows = grid
+            .iter_rows()
+            .map(|row| row.map(|&c| c == b'#').collect())
+            .collect();
+        Self::Picture(Grid::from_rows(rows))
+    }
+}
+
 impl From<&str> for Solution {
     fn from(sol: &str) -> Self {
         Self::Str(sol.to_owned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HI: &str = "\
+#..#.###
+#..#..#.
+####..#.
+#..#..#.
+#..#..#.
+#..#.###";
+
+    #[test]
+    fn test_picture_display() {
+        let sol: Solution = ocr::parse_picture(HI).into();
+        assert_eq!(sol.to_string(), "HI");
+        assert_eq!(sol.picture().as_deref(), Some(HI));
+    }
+
+    #[test]
+    fn test_picture_from_u8_grid() {
+        let sol: Solution = Grid::parse(HI).into();
+        assert_eq!(sol.to_string(), "HI");
+    }
+
+    #[test]
+    fn test_unrecognized_picture_display() {
+        let sol: Solution = ocr::parse_picture("#.\n.#").into();
+        assert_eq!(sol.to_string(), "#./.#");
+    }
+
+    fn parse(s: &str) -> Solution {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_numeric_equality() {
+        assert_eq!(Solution::from(7u8), Solution::from(7i128));
+        assert_eq!(Solution::from(7usize), Solution::from(BigInt::from(7)));
+        assert_eq!(Solution::from(7.0), Solution::from(7u64));
+        assert_ne!(Solution::from(7.5), Solution::from(7u64));
+        assert_ne!(Solution::from(-1i32), Solution::from(u64::MAX));
+        assert_ne!(Solution::from("7"), Solution::from(7u64));
+        assert_eq!(Solution::from(f64::NAN), Solution::from(f64::NAN));
+    }
+
+    #[test]
+    fn test_hash_consistent_with_eq() {
+        use std::collections::HashSet;
+        let set: HashSet<Solution> = [
+            Solution::from(7u8),
+            Solution::from(7i64),
+            Solution::from(7.0),
+            Solution::from(BigInt::from(7)),
+            Solution::from("7"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_from_str_infers_variant() {
+        assert!(matches!(parse("007"), I64(7)));
+        assert!(matches!(parse("-42"), I64(-42)));
+        assert!(matches!(parse("18446744073709551615"), U64(u64::MAX)));
+        assert!(matches!(
+            parse("-170141183460469231731687303715884105728"),
+            I128(i128::MIN)
+        ));
+        assert!(matches!(
+            parse("340282366920938463463374607431768211455"),
+            U128(u128::MAX)
+        ));
+        assert!(matches!(
+            parse("340282366920938463463374607431768211456"),
+            Big(_)
+        ));
+        assert!(matches!(parse("0.25"), Float(0.25)));
+        assert!(matches!(parse("HI"), Str(_)));
+        assert!(matches!(parse("inf"), Str(_)));
+        assert!(matches!(parse("#./.#"), Picture(_)));
+        assert!(matches!(parse("#./#"), Str(_)));
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for sol in [
+            Solution::from(u128::MAX),
+            Solution::from(-3i8),
+            Solution::from(0.1),
+            Solution::from("abc"),
+            ocr::parse_picture("#.\n.#").into(),
+        ] {
+            assert_eq!(parse(&sol.to_string()), sol);
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(Solution::from(7u64).matches(&parse("007")));
+        assert!(Solution::from(0.1 + 0.2).matches(&parse("0.3")));
+        assert!(Solution::from(1e12 + 1e-4).matches(&parse("1000000000000")));
+        assert!(!Solution::from(0.31).matches(&parse("0.3")));
+        assert!(Solution::from(ocr::parse_picture(HI)).matches(&parse("HI")));
+        assert!(!Solution::from(7u64).matches(&parse("8")));
+    }
+
+    #[test]
+    fn test_serde() {
+        let sol = Solution::from(42u64);
+        let json = serde_json::to_string(&sol).unwrap();
+        assert_eq!(json, "{\"U64\":42}");
+        assert_eq!(sol.variant(), "U64");
+        assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), sol);
+
+        let big = Solution::from(BigInt::from(u128::MAX) * BigInt::from(2));
+        let json = serde_json::to_string(&big).unwrap();
+        assert_eq!(
+            json,
+            "{\"Big\":\"680564733841876926926749214863536422910\"}"
+        );
+        assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), big);
+
+        let picture = Solution::from(ocr::parse_picture("#.\n.#"));
+        let json = serde_json::to_string(&picture).unwrap();
+        assert_eq!(json, "{\"Picture\":[[true,false],[false,true]]}");
+    }
+}
@@ -0,0 +1,309 @@
+//! Frames that solvers can emit to show their progress, played back by the runner under
+//! `--visualize`. When visualization is off, [`emit`] is a single atomic load and the frame
+//! is never built.
+
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::Grid;
+use crate::position::Position;
+
+/// Colors for highlight layers in the terminal, cycled if there are more layers
+const LAYER_COLORS: &[&str] = &["\x1b[1;33m", "\x1b[1;36m", "\x1b[1;35m", "\x1b[1;32m"];
+const RESET: &str = "\x1b[0m";
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// A snapshot of a solver's grid, with highlighted cells drawn over it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub cells: Grid<char>,
+    /// Highlight layers, later layers are drawn over earlier ones
+    pub overlays: Vec<Overlay>,
+}
+
+/// Cells highlighted in a frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlay {
+    /// Character shown in place of each highlighted cell
+    pub glyph: char,
+    pub positions: Vec<Position>,
+}
+
+impl Frame {
+    pub fn new<T: Clone + Into<char>>(grid: &Grid<T>) -> Self {
+        let rows = grid
+            .iter_rows()
+            .map(|row| row.map(|cell| cell.clone().into()).collect())
+            .collect();
+        Self {
+            caption: String::new(),
+            cells: Grid::from_rows(rows),
+            overlays: Vec::new(),
+        }
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Add a highlight layer showing `glyph` at each position. Positions off the grid are
+    /// dropped.
+    pub fn highlight(mut self, positions: impl IntoIterator<Item = Position>, glyph: char) -> Self {
+        let positions = positions
+            .into_iter()
+            .filter(|&pos| self.cells.contains(pos))
+            .collect();
+        self.overlays.push(Overlay { glyph, positions });
+        self
+    }
+
+    /// Each cell's character and the index of the topmost layer highlighting it
+    fn layered(&self) -> Grid<(char, Option<usize>)> {
+        let mut layered = Grid::from_rows(
+            self.cells
+                .iter_rows()
+                .map(|row| row.map(|&c| (c, None)).collect())
+                .collect(),
+        );
+        for (layer, overlay) in self.overlays.iter().enumerate() {
+            for &pos in &overlay.positions {
+                layered[pos] = (overlay.glyph, Some(layer));
+            }
+        }
+        layered
+    }
+
+    /// Render with highlight layers colored, for playing in a terminal
+    pub fn render_ansi(&self) -> String {
+        let layered = self.layered();
+        let mut out = String::new();
+        for row in layered.iter_rows() {
+            for &(c, layer) in row {
+                match layer {
+                    Some(layer) => {
+                        out.push_str(LAYER_COLORS[layer % LAYER_COLORS.len()]);
+                        out.push(c);
+                        out.push_str(RESET);
+                    }
+                    None => out.push(c),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Plain text rendering, caption first, with highlight glyphs in place of the cells
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.caption.is_empty() {
+            writeln!(f, "{}", self.caption)?;
+        }
+        for row in self.layered().iter_rows() {
+            let line: String = row.map(|&(c, _)| c).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of captures in progress, checked first so that `emit` stays a single atomic
+/// load when nothing is recording
+static CAPTURING: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Frames recorded by the capture running on this thread
+    static FRAMES: RefCell<Option<Vec<Frame>>> = const { RefCell::new(None) };
+}
+
+/// Whether frames are being recorded on this thread. Solvers can check this to skip
+/// bookkeeping that is only needed for frames.
+#[inline]
+pub fn enabled() -> bool {
+    CAPTURING.load(Ordering::Relaxed) > 0 && FRAMES.with(|frames| frames.borrow().is_some())
+}
+
+/// Record a frame, built only if visualization is on
+#[inline]
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    if enabled() {
+        let frame = frame();
+        FRAMES.with(|frames| {
+            if let Some(frames) = frames.borrow_mut().as_mut() {
+                frames.push(frame);
+            }
+        });
+    }
+}
+
+/// A capture in progress on this thread. Dropping it ends the capture, also when the
+/// captured code panics, and gives the enclosing capture's frames back.
+struct Capture {
+    /// Frames of the enclosing capture, taken once the capture has ended
+    outer: Option<Option<Vec<Frame>>>,
+}
+
+impl Capture {
+    fn start() -> Self {
+        let outer = FRAMES.with(|frames| frames.replace(Some(Vec::new())));
+        CAPTURING.fetch_add(1, Ordering::Relaxed);
+        Self { outer: Some(outer) }
+    }
+
+    /// End the capture, returning its frames
+    fn finish(mut self) -> Vec<Frame> {
+        self.restore().unwrap_or_default()
+    }
+
+    /// Put the enclosing capture's frames back, returning this capture's
+    fn restore(&mut self) -> Option<Vec<Frame>> {
+        let outer = self.outer.take()?;
+        FRAMES.with(|frames| frames.replace(outer))
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        self.restore();
+        CAPTURING.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Run `f` with visualization on, returning its result and the frames it emitted.
+/// Only frames emitted from the calling thread are recorded.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+    let capture = Capture::start();
+    let result = f();
+    (result, capture.finish())
+}
+
+/// How to play frames in the terminal
+#[derive(Debug, Clone, Copy)]
+pub struct Playback {
+    pub fps: u32,
+    /// Wait for Enter between frames instead of animating
+    pub step: bool,
+}
+
+/// Play frames as a terminal animation. In step mode, Enter shows the next frame, `c`
+/// continues as an animation and `q` stops.
+pub fn play(frames: &[Frame], playback: Playback) -> io::Result<()> {
+    let delay = Duration::from_secs_f64(1.0 / f64::from(playback.fps.max(1)));
+    let mut step = playback.step;
+    let mut stdout = io::stdout().lock();
+    let mut stdin = io::stdin().lock();
+
+    for (i, frame) in frames.iter().enumerate() {
+        write!(stdout, "{CLEAR_SCREEN}")?;
+        writeln!(
+            stdout,
+            "frame {}/{}  {}",
+            i + 1,
+            frames.len(),
+            frame.caption
+        )?;
+        write!(stdout, "{}", frame.render_ansi())?;
+
+        if step {
+            write!(stdout, "[enter] next  [c] continue  [q] quit ")?;
+            stdout.flush()?;
+            let mut line = String::new();
+            stdin.read_line(&mut line)?;
+            match line.trim() {
+                "q" => break,
+                "c" => step = false,
+                _ => {}
+            }
+        } else {
+            stdout.flush()?;
+            thread::sleep(delay);
+        }
+    }
+    Ok(())
+}
+
+/// Write frames to `dir` as numbered text files, `frame_00001.txt` and so on
+pub fn export(frames: &[Frame], dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, frame) in frames.iter().enumerate() {
+        fs::write(
+            dir.join(format!("frame_{:05}.txt", i + 1)),
+            frame.to_string(),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::parse("..#\n#..");
+        let frame = Frame::new(&grid)
+            .caption("step 1")
+            .highlight([Position::new(0, 0), Position::new(5, 5)], 'x')
+            .highlight([Position::new(1, 1)], '|');
+        assert_eq!(frame.overlays[0].positions, [Position::new(0, 0)]);
+        assert_eq!(frame.to_string(), "step 1\nx.#\n#|.\n");
+        assert_eq!(
+            frame.render_ansi(),
+            "\x1b[1;33mx\x1b[0m.#\n#\x1b[1;36m|\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    fn test_capture() {
+        let (result, frames) = capture(|| {
+            emit(|| Frame::new(&Grid::parse("#")).caption("test_capture"));
+            7
+        });
+        assert_eq!(result, 7);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].caption, "test_capture");
+    }
+
+    #[test]
+    fn test_capture_panic() {
+        let (_, outer) = capture(|| {
+            emit(|| Frame::new(&Grid::parse("#")).caption("outer"));
+            let panicked = std::panic::catch_unwind(|| capture(|| panic!("solver bug")));
+            assert!(panicked.is_err());
+            emit(|| Frame::new(&Grid::parse("#")).caption("outer"));
+        });
+        assert_eq!(outer.len(), 2);
+        assert!(!enabled());
+        emit(|| panic!("frames aren't built after a panicked capture"));
+    }
+
+    #[test]
+    fn test_disabled() {
+        emit(|| panic!("frames aren't built outside a capture"));
+        assert!(!enabled());
+    }
+
+    #[test]
+    fn test_export() {
+        let dir = std::env::temp_dir().join(format!("visualize-test-{}", std::process::id()));
+        let frames = [
+            Frame::new(&Grid::parse("#.")),
+            Frame::new(&Grid::parse(".#")),
+        ];
+        export(&frames, &dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("frame_00002.txt")).unwrap(),
+            ".#\n"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,35 @@
+//! Regression fixtures: anonymized inputs made by `anonymize <day>`, with the answers they
+//! had when they were made.
+
+use std::path::Path;
+
+use advent_2025::answers::{ANSWERS_FILE, load_answers};
+use advent_2025::runner::{self, Outcome};
+
+#[test]
+fn fixtures_keep_their_answers() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let answers = load_answers(dir.join(ANSWERS_FILE)).unwrap();
+    assert!(!answers.is_empty(), "no fixtures in {}", dir.display());
+
+    let mut days: Vec<u8> = answers.keys().copied().collect();
+    days.sort();
+    for day in days {
+        let path = dir.join(format!("day{}.txt", day));
+        let input = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("cannot read {}: {err}", path.display()));
+        let result = runner::solve(day, &input, None);
+        assert!(
+            matches!(result.outcome, Outcome::Solved { .. }),
+            "day {day}: {:?}",
+            result.outcome
+        );
+        for (part, answer) in result.answers() {
+            assert!(
+                answers[&day].matches(part, answer),
+                "day {day} part {part}: got {answer}, fixture has {}",
+                answers[&day].part(part)
+            );
+        }
+    }
+}
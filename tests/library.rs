@@ -0,0 +1,30 @@
+//! The solutions are usable as a library, without going through the CLI.
+
+use advent_2025::runner::{self, Outcome};
+use advent_2025::solution::{Day, Solution};
+use advent_2025::{day5, days, get_day_solvers};
+
+const DAY5_INPUT: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32";
+
+#[test]
+fn every_day_is_registered() {
+    for solvers in days() {
+        assert_eq!(get_day_solvers(solvers.day).unwrap().day, solvers.day);
+    }
+}
+
+#[test]
+fn solve_through_runner() {
+    let result = runner::solve(5, DAY5_INPUT, None);
+    assert!(matches!(result.outcome, Outcome::Solved { .. }));
+    assert_eq!(result.part(1), Some(&Solution::from(3usize)));
+    assert_eq!(result.part(2), Some(&Solution::from(14usize)));
+}
+
+#[test]
+fn solve_day_directly() {
+    assert_eq!(
+        day5::Solution::part1(DAY5_INPUT).unwrap(),
+        Solution::from(3usize)
+    );
+}
@@ -0,0 +1,42 @@
+//! Plugins built as separate libraries join the registry. Uses the `plugin_day` example,
+//! which `cargo test` builds alongside the tests.
+
+use std::path::PathBuf;
+
+use advent_2025::runner::{self, Outcome};
+use advent_2025::{get_day_solvers, plugin};
+
+/// Directory of the built examples, `target/<profile>/examples`
+fn examples_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().join("examples")
+}
+
+#[test]
+fn plugin_days_join_the_registry() {
+    let dir = std::env::temp_dir().join(format!("plugins-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let name = format!(
+        "{}plugin_day.{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_EXTENSION
+    );
+    std::fs::copy(examples_dir().join(&name), dir.join(&name))
+        .unwrap_or_else(|err| panic!("{name} isn't built, run the whole `cargo test`: {err}"));
+
+    let errors = plugin::load(&dir);
+    assert!(errors.is_empty(), "{errors:?}");
+    assert!(get_day_solvers(1).is_some());
+    assert!(get_day_solvers(12).unwrap().source.starts_with("plugin "));
+
+    let result = runner::solve(12, "1 2\n\n3\n", None);
+    assert!(matches!(result.outcome, Outcome::Solved { .. }));
+    assert_eq!(result.part(1).unwrap().to_string(), "2");
+    assert_eq!(result.part(2).unwrap().to_string(), "6");
+
+    // errors, including panics, come back as failures
+    let result = runner::solve(12, "1 x", Some(2));
+    assert!(matches!(result.outcome, Outcome::Failed(_)));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}